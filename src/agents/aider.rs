@@ -0,0 +1,486 @@
+/*!
+ * AI Context Management Tool - Aider Agent
+ *
+ * Aider agent implementation
+ * Specification: https://aider.chat/docs/usage/conventions.html
+ *
+ * Merged mode: Single CONVENTIONS.md file
+ * Split mode: Multiple .md files in conventions/ folder
+ * Both modes list the generated files under `read:` in .aider.conf.yml
+ */
+
+use crate::core::MarkdownMerger;
+use crate::types::{AIContextConfig, GeneratedFile, OutputMode};
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::path::Path;
+use tokio::fs;
+
+/// Aider configuration file that lists conventions files to read
+const AIDER_CONF_FILE: &str = ".aider.conf.yml";
+
+/// Aider agent
+pub struct AiderAgent {
+    config: AIContextConfig,
+    base_dir: Option<String>,
+}
+
+impl AiderAgent {
+    /// Create a new Aider agent
+    pub fn new(config: AIContextConfig) -> Self {
+        Self {
+            config,
+            base_dir: None,
+        }
+    }
+
+    /// Create Aider agent with specified base directory
+    #[cfg(test)]
+    pub fn new_with_base_dir(config: AIContextConfig, base_dir: String) -> Self {
+        Self {
+            config,
+            base_dir: Some(base_dir),
+        }
+    }
+
+    /// Generate files for Aider
+    /// Existing output is left untouched; call `clean_output` before writing
+    pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "aider");
+
+        // Conventions files as (path relative to the project root, content)
+        let conventions = match self.config.get_effective_output_mode("aider") {
+            OutputMode::Merged => {
+                // Links are rewritten relative to the conventions file's directory
+                let output_path = self.output_path(self.get_conventions_file());
+                let output_dir = match Path::new(&output_path).parent() {
+                    Some(parent) if !parent.as_os_str().is_empty() => {
+                        parent.to_string_lossy().to_string()
+                    }
+                    _ => ".".to_string(),
+                };
+                self.generate_merged(&merger.with_output_dir(output_dir))
+                    .await?
+            }
+            OutputMode::Split => {
+                let output_dir = self.output_path(self.get_conventions_dir());
+                self.generate_split(&merger.with_output_dir(output_dir))
+                    .await?
+            }
+        };
+
+        let read_paths: Vec<String> = conventions.iter().map(|(path, _)| path.clone()).collect();
+        let mut generated_files: Vec<GeneratedFile> = conventions
+            .into_iter()
+            .map(|(path, content)| GeneratedFile::new(self.output_path(&path), content))
+            .collect();
+
+        let conf_path = self.output_path(AIDER_CONF_FILE);
+        // Only a missing file starts empty; anything else would lose the user's settings
+        let existing = match fs::read_to_string(&conf_path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {conf_path}")),
+        };
+        let conf_content = self
+            .update_read_list(&existing, &read_paths)
+            .with_context(|| format!("Failed to update {conf_path}"))?;
+        generated_files.push(GeneratedFile::support(conf_path, conf_content));
+
+        Ok(generated_files)
+    }
+
+    /// Delete previous output (run after generated content passes all checks)
+    pub async fn clean_output(&self) -> Result<()> {
+        match self.config.get_effective_output_mode("aider") {
+            OutputMode::Merged => Ok(()),
+            OutputMode::Split => {
+                self.prepare_conventions_directory(&self.output_path(self.get_conventions_dir()))
+                    .await
+            }
+        }
+    }
+
+    /// Merged mode: Single conventions file
+    async fn generate_merged(&self, merger: &MarkdownMerger) -> Result<Vec<(String, String)>> {
+        let content = merger.merge_all_with_options(Some("aider")).await?;
+        Ok(vec![(self.get_conventions_file().to_string(), content)])
+    }
+
+    /// Split mode: Multiple .md files in the conventions folder
+    async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<(String, String)>> {
+        let docs = merger.get_split_documents().await?;
+        let conventions_dir = self.get_conventions_dir();
+
+        Ok(docs
+            .into_iter()
+            .map(|doc| {
+                let base_name = self.config.strip_source_extension(&doc.path);
+                let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
+                (format!("{conventions_dir}/{safe_name}.md"), doc.content)
+            })
+            .collect())
+    }
+
+    /// Replace the `read:` list of .aider.conf.yml content, keeping other settings and comments
+    /// Entries outside aicm's conventions file and directory are kept ahead of the generated files
+    fn update_read_list(&self, existing: &str, read_paths: &[String]) -> Result<String> {
+        let parsed: Value = if existing.trim().is_empty() {
+            Value::Mapping(Mapping::new())
+        } else {
+            serde_yaml::from_str(existing)?
+        };
+        let Value::Mapping(mut settings) = parsed else {
+            anyhow::bail!("expected a mapping of Aider settings");
+        };
+
+        let current = match settings.get("read") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::String(path)) => vec![path.clone()],
+            Some(Value::Sequence(paths)) => paths
+                .iter()
+                .map(|path| {
+                    path.as_str()
+                        .map(str::to_string)
+                        .context("expected `read:` entries to be strings")
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Some(_) => anyhow::bail!("expected `read:` to be a file path or a list of file paths"),
+        };
+
+        let mut read: Vec<String> = current
+            .into_iter()
+            .filter(|path| !self.is_generated_path(path))
+            .collect();
+        for path in read_paths {
+            if !read.contains(path) {
+                read.push(path.clone());
+            }
+        }
+
+        let read_key = Value::String("read".to_string());
+        let read = Value::Sequence(read.into_iter().map(Value::String).collect());
+        let mut block = Mapping::new();
+        block.insert(read_key.clone(), read.clone());
+        let block = serde_yaml::to_string(&block)?;
+        let edited = replace_top_level_key(existing, "read", &block);
+
+        // The line-based edit keeps comments; layouts it cannot follow (e.g. comments between
+        // `read:` entries) are caught by re-parsing and fall back to re-serializing the settings
+        settings.insert(read_key, read);
+        let expected = Value::Mapping(settings);
+        if serde_yaml::from_str::<Value>(&edited).is_ok_and(|value| value == expected) {
+            return Ok(edited);
+        }
+        eprintln!(
+            "Warning: could not update `read:` in place; rewriting {AIDER_CONF_FILE} without comments"
+        );
+        Ok(serde_yaml::to_string(&expected)?)
+    }
+
+    /// Check whether a `read:` entry points at a file aicm writes
+    fn is_generated_path(&self, path: &str) -> bool {
+        let path = path.trim_start_matches("./");
+        let conventions_dir = self.get_conventions_dir().trim_end_matches('/');
+        path == self.get_conventions_file().trim_start_matches("./")
+            || path
+                .strip_prefix(conventions_dir.trim_start_matches("./"))
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Get conventions file for merged mode
+    fn get_conventions_file(&self) -> &str {
+        self.config
+            .agents
+            .aider
+            .get_advanced_config()
+            .and_then(|config| config.conventions_file.as_deref())
+            .unwrap_or("CONVENTIONS.md")
+    }
+
+    /// Get conventions directory for split mode
+    fn get_conventions_dir(&self) -> &str {
+        self.config
+            .agents
+            .aider
+            .get_advanced_config()
+            .and_then(|config| config.conventions_dir.as_deref())
+            .unwrap_or("conventions")
+    }
+
+    /// Get output path of a project-relative path
+    fn output_path(&self, path: &str) -> String {
+        if let Some(base_dir) = &self.base_dir {
+            format!("{base_dir}/{path}")
+        } else {
+            path.to_string()
+        }
+    }
+
+    /// Prepare conventions directory (delete existing files)
+    async fn prepare_conventions_directory(&self, conventions_dir: &str) -> Result<()> {
+        // Delete contents if directory exists
+        if fs::metadata(conventions_dir).await.is_ok() {
+            let mut entries = fs::read_dir(conventions_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+                    fs::remove_file(path).await?;
+                }
+            }
+        }
+
+        // Create directory (if it doesn't exist)
+        fs::create_dir_all(conventions_dir).await?;
+        Ok(())
+    }
+}
+
+/// Replace a top-level YAML key (with its indented or list continuation lines) by `block`
+/// The block is appended when the key is missing
+fn replace_top_level_key(content: &str, key: &str, block: &str) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let is_key_line = |line: &str| {
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+    };
+
+    let Some(start) = lines.iter().position(|line| is_key_line(line)) else {
+        let mut output = content.to_string();
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(block);
+        return output;
+    };
+
+    // Continuation lines are indented or compact list items; trailing blank lines stay in place
+    let mut end = start + 1;
+    for (index, line) in lines.iter().enumerate().skip(start + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with([' ', '\t']) || line.starts_with("- ") || line.trim_end() == "-" {
+            end = index + 1;
+        } else {
+            break;
+        }
+    }
+
+    let mut output: String = lines[..start].concat();
+    output.push_str(block);
+    output.push_str(&lines[end..].concat());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AgentConfig, AiderAgentConfig, AiderConfig};
+    use tempfile::tempdir;
+    use tokio::fs;
+
+    fn create_test_config(base_dir: &str, output_mode: OutputMode) -> AIContextConfig {
+        AIContextConfig {
+            version: "1.0".to_string(),
+            output_mode: Some(output_mode),
+            include_filenames: Some(false),
+            base_docs_dir: base_dir.to_string(),
+            agents: AgentConfig::default(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_merged_creates_conf() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style\nUse tabs.")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Merged);
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = AiderAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, format!("{base_dir}/CONVENTIONS.md"));
+        assert!(files[0].content.contains("Use tabs."));
+        assert_eq!(files[1].path, format!("{base_dir}/.aider.conf.yml"));
+        assert_eq!(files[1].content, "read:\n- CONVENTIONS.md\n");
+        // Aider settings are not context: no token budget or secret scan
+        assert!(files[1].support);
+    }
+
+    #[tokio::test]
+    async fn test_generate_split_updates_existing_conf() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("api")).await.unwrap();
+        fs::write(docs_dir.join("api/errors.md"), "# Errors")
+            .await
+            .unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style")
+            .await
+            .unwrap();
+        fs::write(
+            temp_dir.path().join(".aider.conf.yml"),
+            "# Aider settings\nmodel: sonnet\nread:\n  - NOTES.md\n  - conventions/removed.md\n  - CONVENTIONS.md\n\nauto-commits: false # keep\n",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split);
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = AiderAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                format!("{base_dir}/conventions/api_errors.md"),
+                format!("{base_dir}/conventions/style.md"),
+                format!("{base_dir}/.aider.conf.yml"),
+            ]
+        );
+        assert_eq!(
+            files[2].content,
+            "# Aider settings\nmodel: sonnet\nread:\n- NOTES.md\n- conventions/api_errors.md\n- conventions/style.md\n\nauto-commits: false # keep\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_custom_conventions_file() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style")
+            .await
+            .unwrap();
+        fs::write(
+            temp_dir.path().join(".aider.conf.yml"),
+            "read: docs/AI.md\nmodel: sonnet\n",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Merged);
+        config.agents.aider = AiderConfig::Advanced(AiderAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            conventions_file: Some("docs/AI.md".to_string()),
+            conventions_dir: None,
+        });
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = AiderAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files[0].path, format!("{base_dir}/docs/AI.md"));
+        assert_eq!(files[1].content, "read:\n- docs/AI.md\nmodel: sonnet\n");
+    }
+
+    #[tokio::test]
+    async fn test_generate_rejects_invalid_read_entry() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join(".aider.conf.yml"),
+            "read:\n  key: value\n",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Merged);
+        let agent =
+            AiderAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        let error = agent.generate().await.unwrap_err();
+        assert!(format!("{error:#}").contains("expected `read:` to be a file path"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_rejects_unreadable_conf() {
+        let temp_dir = tempdir().unwrap();
+        let conf_path = temp_dir.path().join(".aider.conf.yml");
+        fs::write(&conf_path, b"model: sonnet # \xff\n")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Merged);
+        let agent =
+            AiderAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        // Invalid UTF-8 is an error instead of an empty file, so settings are not overwritten
+        let error = agent.generate().await.unwrap_err();
+        assert!(format!("{error:#}").contains("Failed to read"));
+        assert_eq!(
+            fs::read(&conf_path).await.unwrap(),
+            b"model: sonnet # \xff\n"
+        );
+    }
+
+    #[test]
+    fn test_update_read_list_layouts() {
+        let config = create_test_config("./docs", OutputMode::Merged);
+        let agent = AiderAgent::new(config);
+        let read_paths = vec!["CONVENTIONS.md".to_string()];
+
+        // Flow-style lists, on one line or several, are replaced in place
+        assert_eq!(
+            agent
+                .update_read_list(
+                    "read: [NOTES.md, CONVENTIONS.md] # docs\nmodel: x\n",
+                    &read_paths
+                )
+                .unwrap(),
+            "read:\n- NOTES.md\n- CONVENTIONS.md\nmodel: x\n"
+        );
+        assert_eq!(
+            agent
+                .update_read_list(
+                    "read: [NOTES.md,\n  CONVENTIONS.md]\nmodel: x\n",
+                    &read_paths
+                )
+                .unwrap(),
+            "read:\n- NOTES.md\n- CONVENTIONS.md\nmodel: x\n"
+        );
+
+        // A comment between entries ends the line-based edit early; the settings are re-serialized
+        let updated = agent
+            .update_read_list(
+                "read:\n- NOTES.md\n# shared\n- TEAM.md\nmodel: x\n",
+                &read_paths,
+            )
+            .unwrap();
+        let parsed: Value = serde_yaml::from_str(&updated).unwrap();
+        assert_eq!(
+            parsed["read"],
+            serde_yaml::from_str::<Value>("[NOTES.md, TEAM.md, CONVENTIONS.md]").unwrap()
+        );
+        assert_eq!(parsed["model"], Value::String("x".to_string()));
+    }
+
+    #[test]
+    fn test_replace_top_level_key() {
+        let block = "read:\n- A.md\n";
+        assert_eq!(replace_top_level_key("", "read", block), block);
+        assert_eq!(
+            replace_top_level_key("model: sonnet", "read", block),
+            "model: sonnet\nread:\n- A.md\n"
+        );
+        assert_eq!(
+            replace_top_level_key("read: [B.md, C.md]\nreader: x\n", "read", block),
+            "read:\n- A.md\nreader: x\n"
+        );
+    }
+}
@@ -4,7 +4,9 @@
  * Simplified base agent common functions
  */
 
+use crate::core::SourceDoc;
 use crate::types::config::ImportFile;
+use crate::types::{AIContextConfig, FrontmatterMode};
 use std::env;
 use std::path::{Path, PathBuf};
 
@@ -158,6 +160,28 @@ impl BaseAgentUtils {
 
         Ok(false)
     }
+
+    /// Render source doc frontmatter keys to append to a generated frontmatter block
+    /// Empty unless source_frontmatter is merge; conflicting keys keep the generated value
+    pub fn source_frontmatter_keys(
+        config: &AIContextConfig,
+        agent: &str,
+        doc: &SourceDoc,
+        generated_keys: &[&str],
+    ) -> anyhow::Result<String> {
+        if config.get_source_frontmatter_mode() != FrontmatterMode::Merge {
+            return Ok(String::new());
+        }
+
+        let (keys, conflicts) = doc.frontmatter.render_extra_keys(generated_keys)?;
+        for key in conflicts {
+            eprintln!(
+                "Warning: {agent}: frontmatter key '{key}' in {} conflicts with generated frontmatter (generated value kept)",
+                doc.path
+            );
+        }
+        Ok(keys)
+    }
 }
 
 #[cfg(test)]
@@ -474,4 +498,31 @@ mod tests {
         // Note: This test depends on actual file existence for canonicalization
         // In real scenarios, the function would compare canonical paths
     }
+
+    #[test]
+    fn test_source_frontmatter_keys() {
+        let (frontmatter, content) =
+            crate::core::split_frontmatter("---\nalwaysApply: true\nauthor: team\n---\nBody")
+                .unwrap();
+        let doc = SourceDoc {
+            path: "rules.md".to_string(),
+            content: content.to_string(),
+            frontmatter: frontmatter.unwrap(),
+        };
+        let generated_keys = ["description", "globs", "alwaysApply"];
+
+        // Strip mode (default) drops all other keys
+        let mut config = AIContextConfig::default();
+        let keys =
+            BaseAgentUtils::source_frontmatter_keys(&config, "cursor", &doc, &generated_keys)
+                .unwrap();
+        assert!(keys.is_empty());
+
+        // Merge mode keeps non-conflicting keys only
+        config.source_frontmatter = Some(FrontmatterMode::Merge);
+        let keys =
+            BaseAgentUtils::source_frontmatter_keys(&config, "cursor", &doc, &generated_keys)
+                .unwrap();
+        assert_eq!(keys, "author: team\n");
+    }
 }
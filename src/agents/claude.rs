@@ -6,7 +6,7 @@
  */
 
 use crate::agents::base::BaseAgentUtils;
-use crate::core::MarkdownMerger;
+use crate::core::{format_doc_section, insert_toc, MarkdownMerger};
 use crate::types::config::ClaudeConfig;
 use crate::types::{AIContextConfig, GeneratedFile};
 use anyhow::Result;
@@ -25,11 +25,8 @@ impl ClaudeAgent {
 
     /// Generate files for Claude (merged mode only)
     pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
-        let base_docs_dir = self
-            .config
-            .get_effective_base_docs_dir("claude")
-            .to_string();
-        let merger = MarkdownMerger::new_with_base_dir(self.config.clone(), base_docs_dir);
+        let merger =
+            MarkdownMerger::new_for_agent(self.config.clone(), "claude").with_output_dir(".");
         self.generate_merged(&merger).await
     }
 
@@ -143,10 +140,11 @@ impl ClaudeAgent {
 
         // Generate content from filtered base docs files
         let include_filenames = self.config.get_effective_include_filenames("claude");
+        let heading_mode = self.config.get_heading_mode();
         for (file_path, file_content) in &filtered_base_docs {
             if include_filenames {
-                let trimmed_content = file_content.trim();
-                content.push_str(&format!("# {file_path}\n\n{trimmed_content}\n\n"));
+                let section = format_doc_section(file_path, file_content, &heading_mode);
+                content.push_str(&format!("{section}\n\n"));
             } else {
                 let trimmed_content = file_content.trim();
                 content.push_str(&format!("{trimmed_content}\n\n"));
@@ -161,10 +159,14 @@ impl ClaudeAgent {
             content.push_str(&import_content);
         }
 
-        Ok(vec![GeneratedFile::new(
-            output_path,
-            content.trim().to_string(),
-        )])
+        let content = content.trim();
+        let content = if self.config.get_effective_toc("claude") {
+            insert_toc(content)
+        } else {
+            content.to_string()
+        };
+
+        Ok(vec![GeneratedFile::new(output_path, content)])
     }
 
     /// Get output path (CLAUDE.md in project root)
@@ -188,6 +190,7 @@ mod tests {
             include_filenames: Some(true),         // Enable headers for testing
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
@@ -365,6 +368,12 @@ mod tests {
             output_mode: None,
             include_filenames: Some(true),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             import_files: vec![
                 ImportFile {
                     path: import_file_path.to_string_lossy().to_string(),
@@ -415,6 +424,12 @@ mod tests {
             output_mode: None,
             include_filenames: Some(false),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             import_files: vec![ImportFile {
                 path: import_file_path.to_string_lossy().to_string(),
                 note: Some("Import only file".to_string()),
@@ -458,6 +473,12 @@ mod tests {
             output_mode: None,
             include_filenames: Some(true),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             import_files: vec![
                 ImportFile {
                     path: duplicate_file_path.to_string_lossy().to_string(),
@@ -510,6 +531,12 @@ mod tests {
             output_mode: None,
             include_filenames: Some(true),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             import_files: vec![], // Empty import files
         });
 
@@ -37,13 +37,42 @@ impl ClineAgent {
     }
 
     /// Generate files for Cline
+    /// Existing output is left untouched; call `clean_output` before writing
     pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
-        let base_docs_dir = self.config.get_effective_base_docs_dir("cline").to_string();
-        let merger = MarkdownMerger::new_with_base_dir(self.config.clone(), base_docs_dir);
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "cline");
 
         match self.config.get_effective_output_mode("cline") {
-            OutputMode::Merged => self.generate_merged(&merger).await,
-            OutputMode::Split => self.generate_split(&merger).await,
+            OutputMode::Merged => {
+                // .clinerules is a file in the project root
+                let output_dir = self.base_dir.clone().unwrap_or_else(|| ".".to_string());
+                self.generate_merged(&merger.with_output_dir(output_dir))
+                    .await
+            }
+            OutputMode::Split => {
+                self.generate_split(&merger.with_output_dir(self.get_split_rules_dir()))
+                    .await
+            }
+        }
+    }
+
+    /// Delete previous output (run after generated content passes all checks)
+    pub async fn clean_output(&self) -> Result<()> {
+        match self.config.get_effective_output_mode("cline") {
+            OutputMode::Merged => {
+                // Delete existing .clinerules directory (for split mode) if it exists
+                let output_path = self.get_merged_output_path();
+                if let Ok(metadata) = fs::metadata(&output_path).await {
+                    if metadata.is_dir() {
+                        fs::remove_dir_all(&output_path).await?;
+                    }
+                }
+                Ok(())
+            }
+            // Prepare .clinerules/ directory
+            OutputMode::Split => {
+                self.prepare_rules_directory(&self.get_split_rules_dir())
+                    .await
+            }
         }
     }
 
@@ -52,36 +81,36 @@ impl ClineAgent {
         let content = merger.merge_all_with_options(Some("cline")).await?;
         let output_path = self.get_merged_output_path();
 
-        // Delete existing .clinerules directory (for split mode) if it exists
-        if let Ok(metadata) = fs::metadata(&output_path).await {
-            if metadata.is_dir() {
-                fs::remove_dir_all(&output_path).await?;
-            }
-        }
-
         Ok(vec![GeneratedFile::new(output_path, content)])
     }
 
     /// Split mode: Multiple .md files in .clinerules/ folder
     async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
-        let files = merger.get_individual_files().await?;
+        let docs = merger.get_split_documents().await?;
         let mut generated_files = Vec::new();
-
-        // Prepare .clinerules/ directory
         let rules_dir = self.get_split_rules_dir();
-        self.prepare_rules_directory(&rules_dir).await?;
 
-        for (file_name, content) in files {
+        // Cline reads rule files alphabetically, so explicit ordering needs number prefixes
+        let numbered = !self.config.get_order_patterns().is_empty()
+            || docs.iter().any(|doc| doc.frontmatter.order.is_some());
+        let width = docs.len().to_string().len().max(2);
+
+        for (index, doc) in docs.into_iter().enumerate() {
             // Create md filename by removing extension from filename
-            let base_name = file_name.trim_end_matches(".md");
+            let base_name = self.config.strip_source_extension(&doc.path);
             let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
 
-            // Use original filename (no number prefix)
-            let output_filename = format!("{safe_name}.md");
+            // Use original filename (number prefix only when ordering is configured)
+            let output_filename = if numbered {
+                let number = index + 1;
+                format!("{number:0width$}_{safe_name}.md")
+            } else {
+                format!("{safe_name}.md")
+            };
 
             generated_files.push(GeneratedFile::new(
                 format!("{rules_dir}/{output_filename}"),
-                content,
+                doc.content,
             ));
         }
 
@@ -144,6 +173,7 @@ mod tests {
             include_filenames: Some(true), // Enable headers for testing
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
@@ -226,6 +256,35 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_generate_split_numbered_when_ordered() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("alpha.md"), "Alpha")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("zeta.md"), "---\norder: 1\n---\nZeta")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        let agent =
+            ClineAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        let files = agent.generate().await.unwrap();
+        let temp_dir_path = temp_dir.path().to_string_lossy();
+        assert_eq!(
+            files[0].path,
+            format!("{temp_dir_path}/.clinerules/01_zeta.md")
+        );
+        assert_eq!(files[0].content, "Zeta");
+        assert_eq!(
+            files[1].path,
+            format!("{temp_dir_path}/.clinerules/02_alpha.md")
+        );
+    }
+
     #[tokio::test]
     async fn test_generate_split_with_subdirectory() {
         let temp_dir = tempdir().unwrap();
@@ -0,0 +1,390 @@
+/*!
+ * AI Context Management Tool - Continue Agent
+ *
+ * Continue agent implementation
+ * Specification: https://docs.continue.dev/customize/deep-dives/rules
+ *
+ * Split mode: Multiple .md files with rule frontmatter in .continue/rules/
+ * Merged mode: Single .continue/rules/context.md file
+ */
+
+use crate::agents::base::BaseAgentUtils;
+use crate::core::{
+    find_cursor_rule, matches_any_pattern, resolve_cursor_rule, select_rule_content,
+    MarkdownMerger, RuleCoverage,
+};
+use crate::types::{
+    AIContextConfig, CursorSplitConfig, CursorSplitRule, GeneratedFile, OutputMode,
+};
+use anyhow::Result;
+use serde_yaml::Value;
+use tokio::fs;
+
+/// Continue agent
+pub struct ContinueAgent {
+    config: AIContextConfig,
+    base_dir: Option<String>,
+}
+
+impl ContinueAgent {
+    /// Create a new Continue agent
+    pub fn new(config: AIContextConfig) -> Self {
+        Self {
+            config,
+            base_dir: None,
+        }
+    }
+
+    /// Create Continue agent with specified base directory
+    #[cfg(test)]
+    pub fn new_with_base_dir(config: AIContextConfig, base_dir: String) -> Self {
+        Self {
+            config,
+            base_dir: Some(base_dir),
+        }
+    }
+
+    /// Generate files for Continue
+    /// Existing output is left untouched; call `clean_output` before writing
+    pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "continue")
+            .with_output_dir(self.get_rules_dir());
+
+        match self.config.get_effective_output_mode("continue") {
+            OutputMode::Merged => self.generate_merged(&merger).await,
+            OutputMode::Split => self.generate_split(&merger).await,
+        }
+    }
+
+    /// Delete previous output (run after generated content passes all checks)
+    pub async fn clean_output(&self) -> Result<()> {
+        // Create .continue/rules/ directory and delete existing files
+        self.prepare_rules_directory(&self.get_rules_dir()).await
+    }
+
+    /// Merged mode: Single always-applied rule file
+    async fn generate_merged(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
+        let content = merger.merge_all_with_options(Some("continue")).await?;
+        let rules_dir = self.get_rules_dir();
+
+        let frontmatter = self.create_frontmatter("context", None)?;
+        Ok(vec![GeneratedFile::new(
+            format!("{rules_dir}/context.md"),
+            format!("---\n{frontmatter}---\n\n{content}"),
+        )])
+    }
+
+    /// Split mode: One rule file per doc
+    async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
+        let docs = merger.get_split_documents().await?;
+        let mut generated_files = Vec::new();
+        let rules_dir = self.get_rules_dir();
+
+        let rules = self
+            .get_split_config()
+            .map(|config| config.rules.as_slice())
+            .unwrap_or_default();
+
+        for doc in &docs {
+            // First matching rule wins
+            let matched_rule = find_cursor_rule(rules, &doc.path)?;
+
+            // A rule with a sections selector outputs only the selected sections
+            let Some(content) = select_rule_content(matched_rule, &doc.content)? else {
+                // No selected section in this doc
+                continue;
+            };
+
+            let base_name = self.config.strip_source_extension(&doc.path);
+            let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
+
+            let rule = resolve_cursor_rule(matched_rule, &doc.frontmatter);
+            let mut frontmatter = self.create_frontmatter(&safe_name, rule.as_ref())?;
+            frontmatter.push_str(&BaseAgentUtils::source_frontmatter_keys(
+                &self.config,
+                "continue",
+                doc,
+                &["name", "description", "globs", "alwaysApply"],
+            )?);
+
+            generated_files.push(GeneratedFile::new(
+                format!("{rules_dir}/{safe_name}.md"),
+                format!("---\n{frontmatter}---\n\n{content}"),
+            ));
+        }
+
+        Ok(generated_files)
+    }
+
+    /// Create frontmatter lines for a rule (no rule: always applied)
+    /// alwaysApply defaults to true only for rules without globs, description or manual
+    fn create_frontmatter(&self, name: &str, rule: Option<&CursorSplitRule>) -> Result<String> {
+        let mut frontmatter = format!("name: {}\n", yaml_scalar(name)?);
+        let Some(rule) = rule else {
+            frontmatter.push_str("alwaysApply: true\n");
+            return Ok(frontmatter);
+        };
+
+        if let Some(description) = &rule.description {
+            frontmatter.push_str(&format!("description: {}\n", yaml_scalar(description)?));
+        }
+        match rule.globs.as_deref() {
+            Some([glob]) => frontmatter.push_str(&format!("globs: \"{glob}\"\n")),
+            Some(globs) if !globs.is_empty() => {
+                frontmatter.push_str("globs:\n");
+                for glob in globs {
+                    frontmatter.push_str(&format!("  - \"{glob}\"\n"));
+                }
+            }
+            _ => {}
+        }
+
+        let always_apply = rule.always_apply.unwrap_or(
+            rule.globs.is_none() && rule.description.is_none() && rule.manual != Some(true),
+        );
+        frontmatter.push_str(&format!("alwaysApply: {always_apply}\n"));
+        Ok(frontmatter)
+    }
+
+    /// Report which split rule each doc matches (None when split rules are not in effect)
+    pub async fn rule_coverage(&self) -> Result<Option<RuleCoverage>> {
+        if self.config.get_effective_output_mode("continue") != OutputMode::Split {
+            return Ok(None);
+        }
+        let Some(split_config) = self.get_split_config() else {
+            return Ok(None);
+        };
+
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "continue");
+        let docs = merger.get_split_documents().await?;
+        let doc_paths: Vec<String> = docs.iter().map(|doc| doc.path.clone()).collect();
+        let patterns: Vec<&[String]> = split_config
+            .rules
+            .iter()
+            .map(|rule| rule.file_patterns.as_slice())
+            .collect();
+
+        let mut coverage =
+            RuleCoverage::build("continue", &doc_paths, &patterns, |file_name, patterns| {
+                matches_any_pattern(file_name, patterns)
+            })?;
+        let frontmatter_docs: Vec<String> = docs
+            .iter()
+            .filter(|doc| doc.frontmatter.has_rule_metadata())
+            .map(|doc| doc.path.clone())
+            .collect();
+        coverage.mark_frontmatter_docs(&frontmatter_docs);
+        Ok(Some(coverage))
+    }
+
+    /// Get split_config setting
+    fn get_split_config(&self) -> Option<&CursorSplitConfig> {
+        self.config
+            .agents
+            .continue_dev
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+    }
+
+    /// Get rules directory path
+    fn get_rules_dir(&self) -> String {
+        if let Some(base_dir) = &self.base_dir {
+            format!("{base_dir}/.continue/rules")
+        } else {
+            ".continue/rules".to_string()
+        }
+    }
+
+    /// Prepare .continue/rules/ directory (delete existing files)
+    async fn prepare_rules_directory(&self, rules_dir: &str) -> Result<()> {
+        // Delete contents if directory exists
+        if fs::metadata(rules_dir).await.is_ok() {
+            let mut entries = fs::read_dir(rules_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+                    fs::remove_file(path).await?;
+                }
+            }
+        }
+
+        // Create directory (if it doesn't exist)
+        fs::create_dir_all(rules_dir).await?;
+        Ok(())
+    }
+}
+
+/// Render a string as a YAML scalar (quoted only when needed)
+fn yaml_scalar(value: &str) -> Result<String> {
+    let rendered = serde_yaml::to_string(&Value::String(value.to_string()))?;
+    Ok(rendered.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AgentConfig, ContinueAgentConfig, ContinueConfig};
+    use tempfile::tempdir;
+    use tokio::fs;
+
+    fn create_test_config(base_dir: &str, output_mode: OutputMode) -> AIContextConfig {
+        AIContextConfig {
+            version: "1.0".to_string(),
+            output_mode: Some(output_mode),
+            include_filenames: Some(false),
+            base_docs_dir: base_dir.to_string(),
+            agents: AgentConfig::default(),
+            ..Default::default()
+        }
+    }
+
+    fn with_rules(mut config: AIContextConfig, rules: Vec<CursorSplitRule>) -> AIContextConfig {
+        config.agents.continue_dev = ContinueConfig::Advanced(ContinueAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(CursorSplitConfig { rules }),
+        });
+        config
+    }
+
+    fn rule(patterns: &[&str]) -> CursorSplitRule {
+        CursorSplitRule {
+            file_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            always_apply: None,
+            globs: None,
+            description: None,
+            manual: None,
+            sections: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_merged() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style\nUse tabs.")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Merged);
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = ContinueAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].path,
+            format!("{base_dir}/.continue/rules/context.md")
+        );
+        assert!(files[0]
+            .content
+            .starts_with("---\nname: context\nalwaysApply: true\n---\n\n"));
+        assert!(files[0].content.contains("Use tabs."));
+    }
+
+    #[tokio::test]
+    async fn test_generate_split_with_rules() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        for name in ["rust.md", "review.md", "release.md", "style.md"] {
+            fs::write(docs_dir.join(name), "# Doc").await.unwrap();
+        }
+
+        let mut glob_rule = rule(&["rust*"]);
+        glob_rule.globs = Some(vec!["**/*.rs".to_string(), "Cargo.toml".to_string()]);
+        let mut description_rule = rule(&["review*"]);
+        description_rule.description = Some("Review: checklist".to_string());
+        let mut manual_rule = rule(&["release*"]);
+        manual_rule.manual = Some(true);
+        let config = with_rules(
+            create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split),
+            vec![glob_rule, description_rule, manual_rule],
+        );
+        let agent =
+            ContinueAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        let files = agent.generate().await.unwrap();
+        let content = |name: &str| {
+            files
+                .iter()
+                .find(|file| file.path.ends_with(name))
+                .map(|file| file.content.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            content("rust.md"),
+            "---\nname: rust\nglobs:\n  - \"**/*.rs\"\n  - \"Cargo.toml\"\nalwaysApply: false\n---\n\n# Doc"
+        );
+        assert!(content("review.md").starts_with(
+            "---\nname: review\ndescription: 'Review: checklist'\nalwaysApply: false\n---\n"
+        ));
+        assert!(content("release.md").starts_with("---\nname: release\nalwaysApply: false\n---\n"));
+        assert!(content("style.md").starts_with("---\nname: style\nalwaysApply: true\n---\n"));
+    }
+
+    #[tokio::test]
+    async fn test_split_doc_frontmatter_overrides_rule() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("api")).await.unwrap();
+        fs::write(
+            docs_dir.join("api/errors.md"),
+            "---\nglobs: \"src/api/**\"\n---\n\n# Errors",
+        )
+        .await
+        .unwrap();
+
+        let mut manual_rule = rule(&["api/*"]);
+        manual_rule.manual = Some(true);
+        let config = with_rules(
+            create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split),
+            vec![manual_rule],
+        );
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = ContinueAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(
+            files[0].path,
+            format!("{base_dir}/.continue/rules/api_errors.md")
+        );
+        assert_eq!(
+            files[0].content,
+            "---\nname: api_errors\nglobs: \"src/api/**\"\nalwaysApply: false\n---\n\n# Errors"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_keeps_existing_files_until_clean_output() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style")
+            .await
+            .unwrap();
+        let rules_dir = temp_dir.path().join(".continue/rules");
+        fs::create_dir_all(&rules_dir).await.unwrap();
+        fs::write(rules_dir.join("old.md"), "old").await.unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split);
+        let agent =
+            ContinueAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        // Generating only builds content; previous output stays in place
+        agent.generate().await.unwrap();
+        assert!(rules_dir.join("old.md").exists());
+
+        agent.clean_output().await.unwrap();
+        assert!(!rules_dir.join("old.md").exists());
+    }
+}
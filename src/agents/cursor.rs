@@ -4,7 +4,8 @@
  * Simplified Cursor agent implementation
  */
 
-use crate::core::MarkdownMerger;
+use crate::agents::base::BaseAgentUtils;
+use crate::core::{resolve_cursor_rule, select_rule_content, MarkdownMerger, RuleCoverage};
 use crate::types::{AIContextConfig, CursorConfig, GeneratedFile, OutputMode};
 use anyhow::Result;
 use tokio::fs;
@@ -34,12 +35,10 @@ impl CursorAgent {
     }
 
     /// Generate files for Cursor
+    /// Existing output is left untouched; call `clean_output` before writing
     pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
-        let base_docs_dir = self
-            .config
-            .get_effective_base_docs_dir("cursor")
-            .to_string();
-        let merger = MarkdownMerger::new_with_base_dir(self.config.clone(), base_docs_dir);
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "cursor")
+            .with_output_dir(self.get_rules_dir());
 
         match self.config.get_effective_output_mode("cursor") {
             OutputMode::Merged => self.generate_merged(&merger).await,
@@ -51,10 +50,7 @@ impl CursorAgent {
     async fn generate_merged(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
         let content = merger.merge_all_with_options(Some("cursor")).await?;
         let mdc_content = self.create_mdc_content(&content);
-
-        // Create .cursor/rules/ directory and delete existing files
         let rules_dir = self.get_rules_dir();
-        self.prepare_rules_directory(&rules_dir).await?;
 
         Ok(vec![GeneratedFile::new(
             format!("{rules_dir}/context.mdc"),
@@ -64,72 +60,93 @@ impl CursorAgent {
 
     /// Split mode: split by file
     async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
-        let files = merger.get_individual_files().await?;
+        let docs = merger.get_split_documents().await?;
         let mut generated_files = Vec::new();
-
-        // Create .cursor/rules/ directory and delete existing files
         let rules_dir = self.get_rules_dir();
-        self.prepare_rules_directory(&rules_dir).await?;
-
-        // Check split_config setting
-        let split_config = self.get_split_config();
 
-        if let Some(config) = split_config {
-            // When split_config setting exists: process files based on rules
-            let mut processed_files = std::collections::HashSet::new();
+        // Check split_config setting (first matching rule wins)
+        let rules = self
+            .get_split_config()
+            .map(|config| config.rules.as_slice())
+            .unwrap_or_default();
+
+        for doc in &docs {
+            let matched_rule = rules
+                .iter()
+                .find(|rule| self.file_matches_patterns(&doc.path, &rule.file_patterns));
+
+            // A rule with a sections selector outputs only the selected sections
+            let Some(content) = select_rule_content(matched_rule, &doc.content)? else {
+                // No selected section in this doc
+                continue;
+            };
 
-            // Process matching files for each rule
-            for rule in &config.rules {
-                for (file_name, content) in &files {
-                    if processed_files.contains(file_name) {
-                        continue;
-                    }
-                    if self.file_matches_patterns(file_name, &rule.file_patterns) {
-                        let mdc_content = self.create_mdc_content_with_rule(content, rule);
-                        let base_name = file_name.trim_end_matches(".md");
-                        let safe_name = base_name.replace(['/', '\\'], "_");
-
-                        generated_files.push(GeneratedFile::new(
-                            format!("{rules_dir}/{safe_name}.mdc"),
-                            mdc_content,
-                        ));
-                        processed_files.insert(file_name.clone());
+            // Unmatched files without frontmatter metadata use the default always rule
+            let mut frontmatter = match resolve_cursor_rule(matched_rule, &doc.frontmatter) {
+                Some(mut rule) => {
+                    // always_apply: false in doc frontmatter with no globs or description: Manual
+                    if doc.frontmatter.always_apply == Some(false)
+                        && rule.globs.is_none()
+                        && rule.description.is_none()
+                    {
+                        rule.manual = Some(true);
                     }
+                    self.create_frontmatter_with_rule(&rule)
                 }
-            }
-
-            // Process unmatched files with default always rule
-            for (file_name, content) in &files {
-                if !processed_files.contains(file_name) {
-                    let mdc_content = self.create_mdc_content(content);
-                    let base_name = file_name.trim_end_matches(".md");
-                    let safe_name = base_name.replace(['/', '\\'], "_");
-
-                    generated_files.push(GeneratedFile::new(
-                        format!("{rules_dir}/{safe_name}.mdc"),
-                        mdc_content,
-                    ));
-                }
-            }
-        } else {
-            // Traditional behavior: convert all files individually (default alwaysApply: true)
-            for (file_name, content) in files {
-                let mdc_content = self.create_mdc_content(&content);
-
-                // Create mdc filename by removing extension from filename
-                let base_name = file_name.trim_end_matches(".md");
-                let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
-
-                generated_files.push(GeneratedFile::new(
-                    format!("{rules_dir}/{safe_name}.mdc"),
-                    mdc_content,
-                ));
-            }
+                None => self.create_frontmatter(),
+            };
+            frontmatter.push_str(&BaseAgentUtils::source_frontmatter_keys(
+                &self.config,
+                "cursor",
+                doc,
+                &["description", "globs", "alwaysApply"],
+            )?);
+            let mdc_content = format!("---\n{frontmatter}---\n\n{content}");
+
+            // Create mdc filename by removing extension from filename
+            let base_name = self.config.strip_source_extension(&doc.path);
+            let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
+
+            generated_files.push(GeneratedFile::new(
+                format!("{rules_dir}/{safe_name}.mdc"),
+                mdc_content,
+            ));
         }
 
         Ok(generated_files)
     }
 
+    /// Report which split rule each doc matches (None when split rules are not in effect)
+    pub async fn rule_coverage(&self) -> Result<Option<RuleCoverage>> {
+        if self.config.get_effective_output_mode("cursor") != OutputMode::Split {
+            return Ok(None);
+        }
+        let Some(split_config) = self.get_split_config() else {
+            return Ok(None);
+        };
+
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "cursor");
+        let docs = merger.get_split_documents().await?;
+        let doc_paths: Vec<String> = docs.iter().map(|doc| doc.path.clone()).collect();
+        let patterns: Vec<&[String]> = split_config
+            .rules
+            .iter()
+            .map(|rule| rule.file_patterns.as_slice())
+            .collect();
+
+        let mut coverage =
+            RuleCoverage::build("cursor", &doc_paths, &patterns, |file_name, patterns| {
+                Ok(self.file_matches_patterns(file_name, patterns))
+            })?;
+        let frontmatter_docs: Vec<String> = docs
+            .iter()
+            .filter(|doc| doc.frontmatter.has_rule_metadata())
+            .map(|doc| doc.path.clone())
+            .collect();
+        coverage.mark_frontmatter_docs(&frontmatter_docs);
+        Ok(Some(coverage))
+    }
+
     /// Get rules directory path
     fn get_rules_dir(&self) -> String {
         if let Some(base_dir) = &self.base_dir {
@@ -139,6 +156,12 @@ impl CursorAgent {
         }
     }
 
+    /// Delete previous output (run after generated content passes all checks)
+    pub async fn clean_output(&self) -> Result<()> {
+        // Create .cursor/rules/ directory and delete existing files
+        self.prepare_rules_directory(&self.get_rules_dir()).await
+    }
+
     /// Prepare .cursor/rules/ directory (delete existing files)
     async fn prepare_rules_directory(&self, rules_dir: &str) -> Result<()> {
         // Delete contents if directory exists
@@ -210,16 +233,6 @@ impl CursorAgent {
         file_name == pattern
     }
 
-    /// Create MDC format content with rule settings
-    fn create_mdc_content_with_rule(
-        &self,
-        markdown_content: &str,
-        rule: &crate::types::CursorSplitRule,
-    ) -> String {
-        let frontmatter = self.create_frontmatter_with_rule(rule);
-        format!("---\n{frontmatter}---\n\n{markdown_content}")
-    }
-
     /// Create YAML frontmatter with rule settings
     fn create_frontmatter_with_rule(&self, rule: &crate::types::CursorSplitRule) -> String {
         // Priority: manual > alwaysApply > globs > description
@@ -260,7 +273,7 @@ impl CursorAgent {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{AgentConfig, OutputMode};
+    use crate::types::{AgentConfig, CursorSplitRule, OutputMode};
     use tempfile::tempdir;
     use tokio::fs;
 
@@ -271,6 +284,7 @@ mod tests {
             include_filenames: Some(true), // Enable headers for testing
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
@@ -320,6 +334,46 @@ mod tests {
         assert!(files[0].content.contains("This is a test."));
     }
 
+    #[tokio::test]
+    async fn test_generate_merged_toc() {
+        use crate::types::CursorAgentConfig;
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+        fs::write(docs_path.join("test.md"), "# Test Content\nThis is a test.")
+            .await
+            .unwrap();
+
+        // The global toc setting does not apply to context.mdc
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Merged);
+        config.toc = Some(true);
+        let agent = CursorAgent::new_with_base_dir(
+            config.clone(),
+            temp_dir.path().to_string_lossy().to_string(),
+        );
+        let files = agent.generate().await.unwrap();
+        assert!(!files[0].content.contains("**Table of Contents**"));
+
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: Some(true),
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: None,
+        });
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+        assert!(files[0].content.contains("**Table of Contents**"));
+        assert!(files[0].content.contains("- [Test Content](#test-content)"));
+    }
+
     #[tokio::test]
     async fn test_generate_split_multiple_files() {
         let temp_dir = tempdir().unwrap();
@@ -396,6 +450,43 @@ mod tests {
         assert!(files[0].content.contains("Nested content"));
     }
 
+    #[tokio::test]
+    async fn test_generate_split_strips_configured_extensions() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("guide.mdx"),
+            "import X from './x'\n\n# Guide",
+        )
+        .await
+        .unwrap();
+        fs::write(docs_path.join("notes.txt"), "Notes")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("image.png"), "binary")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.source_extensions = Some(vec!["mdx".to_string(), "txt".to_string()]);
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        let files = agent.generate().await.unwrap();
+        let rules_dir = format!("{}/.cursor/rules", temp_dir.path().to_string_lossy());
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                format!("{rules_dir}/guide.mdc"),
+                format!("{rules_dir}/notes.mdc")
+            ]
+        );
+        assert!(files[0].content.ends_with("# Guide"));
+        assert!(!files[0].content.contains("import X"));
+    }
+
     #[tokio::test]
     async fn test_create_mdc_content() {
         let config = create_test_config("./docs", OutputMode::Merged);
@@ -449,6 +540,26 @@ mod tests {
         assert!(rules_dir.is_dir());
     }
 
+    #[tokio::test]
+    async fn test_generate_keeps_existing_files_until_clean_output() {
+        let temp_dir = tempdir().unwrap();
+        let rules_dir = temp_dir.path().join(".cursor/rules");
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Split);
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        fs::create_dir_all(&rules_dir).await.unwrap();
+        let existing_mdc = rules_dir.join("old_file.mdc");
+        fs::write(&existing_mdc, "old content").await.unwrap();
+
+        // Generating only builds content; previous output stays in place
+        agent.generate().await.unwrap();
+        assert!(existing_mdc.exists());
+
+        agent.clean_output().await.unwrap();
+        assert!(!existing_mdc.exists());
+    }
+
     #[tokio::test]
     async fn test_prepare_rules_directory_removes_existing_mdc_files() {
         let temp_dir = tempdir().unwrap();
@@ -568,10 +679,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["*manual*".to_string()],
                     manual: Some(true),
+                    sections: None,
                     always_apply: None,
                     globs: None,
                     description: None,
@@ -608,10 +726,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["*always*".to_string()],
                     manual: None,
+                    sections: None,
                     always_apply: Some(true),
                     globs: None,
                     description: None,
@@ -647,10 +772,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["*rust*".to_string()],
                     manual: None,
+                    sections: None,
                     always_apply: None,
                     globs: Some(vec!["**/*.rs".to_string()]),
                     description: None,
@@ -686,10 +818,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["*agent*".to_string()],
                     manual: None,
+                    sections: None,
                     always_apply: None,
                     globs: None,
                     description: Some("Agent requested rule".to_string()),
@@ -727,10 +866,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["*multi*".to_string()],
                     manual: None,
+                    sections: None,
                     always_apply: None,
                     globs: Some(vec!["**/*.rs".to_string(), "**/*.toml".to_string()]),
                     description: None,
@@ -766,10 +912,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["matched.md".to_string()],
                     manual: Some(true),
+                    sections: None,
                     always_apply: None,
                     globs: None,
                     description: None,
@@ -833,10 +986,17 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![CursorSplitRule {
                     file_patterns: vec!["*priority*".to_string()],
                     manual: Some(true),
+                    sections: None,
                     always_apply: Some(true),
                     globs: Some(vec!["**/*.rs".to_string()]),
                     description: Some("Test description".to_string()),
@@ -871,11 +1031,18 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(CursorSplitConfig {
                 rules: vec![
                     CursorSplitRule {
                         file_patterns: vec!["*dup*".to_string()],
                         manual: Some(true),
+                        sections: None,
                         always_apply: None,
                         globs: None,
                         description: None,
@@ -883,6 +1050,7 @@ mod tests {
                     CursorSplitRule {
                         file_patterns: vec!["dup.md".to_string()],
                         manual: Some(true),
+                        sections: None,
                         always_apply: None,
                         globs: None,
                         description: None,
@@ -897,4 +1065,347 @@ mod tests {
 
         assert_eq!(files.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_rule_coverage() {
+        use crate::types::{CursorAgentConfig, CursorSplitConfig, CursorSplitRule};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("project.md"), "Project")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("notes.md"), "Notes")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: Some(OutputMode::Split),
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(CursorSplitConfig {
+                rules: vec![
+                    CursorSplitRule {
+                        file_patterns: vec!["*project*".to_string()],
+                        manual: None,
+                        sections: None,
+                        always_apply: Some(true),
+                        globs: None,
+                        description: None,
+                    },
+                    CursorSplitRule {
+                        file_patterns: vec!["*rust*".to_string()],
+                        manual: None,
+                        sections: None,
+                        always_apply: None,
+                        globs: Some(vec!["**/*.rs".to_string()]),
+                        description: None,
+                    },
+                ],
+            }),
+        });
+
+        let agent = CursorAgent::new(config);
+        let coverage = agent.rule_coverage().await.unwrap().unwrap();
+
+        assert_eq!(coverage.rules[0].matched_docs, vec!["project.md"]);
+        assert!(coverage.rules[1].matched_docs.is_empty());
+        assert_eq!(coverage.fallback_docs, vec!["notes.md"]);
+    }
+
+    #[tokio::test]
+    async fn test_rule_coverage_without_split_config() {
+        let config = create_test_config("./docs", OutputMode::Split);
+        let agent = CursorAgent::new(config);
+
+        assert!(agent.rule_coverage().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_split_doc_frontmatter_overrides_rule() {
+        use crate::types::{CursorAgentConfig, CursorSplitConfig};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("rust-guide.md"),
+            "---\nglobs: [\"**/*.rs\"]\nalways_apply: false\n---\n# Rust",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("api.md"),
+            "---\ndescription: API design rules\n---\n# API",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: Some(OutputMode::Split),
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(CursorSplitConfig {
+                rules: vec![CursorSplitRule {
+                    file_patterns: vec!["*rust*".to_string()],
+                    manual: None,
+                    sections: None,
+                    always_apply: Some(true),
+                    globs: None,
+                    description: None,
+                }],
+            }),
+        });
+
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        let rust_file = files
+            .iter()
+            .find(|f| f.path.contains("rust-guide"))
+            .unwrap();
+        assert_eq!(
+            rust_file.content,
+            "---\ndescription:\nglobs: **/*.rs\nalwaysApply: false\n---\n\n# Rust"
+        );
+
+        // Doc without matching rule uses its own frontmatter
+        let api_file = files.iter().find(|f| f.path.contains("api")).unwrap();
+        assert!(api_file
+            .content
+            .starts_with("---\ndescription: API design rules\nglobs:\nalwaysApply: false\n---"));
+    }
+
+    #[tokio::test]
+    async fn test_split_always_apply_false() {
+        use crate::types::{CursorAgentConfig, CursorSplitConfig};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("notes.md"), "# Notes")
+            .await
+            .unwrap();
+        fs::write(
+            docs_path.join("manual.md"),
+            "---\nalways_apply: false\n---\n# Manual",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: Some(OutputMode::Split),
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(CursorSplitConfig {
+                rules: vec![CursorSplitRule {
+                    file_patterns: vec!["*notes*".to_string()],
+                    manual: None,
+                    sections: None,
+                    always_apply: Some(false),
+                    globs: None,
+                    description: None,
+                }],
+            }),
+        });
+
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+
+        // A config rule with only always_apply: false keeps the default always rule
+        let notes_file = files.iter().find(|f| f.path.contains("notes")).unwrap();
+        assert!(notes_file.content.contains("alwaysApply: true"));
+
+        // Doc frontmatter always_apply: false makes the doc a manual rule
+        let manual_file = files.iter().find(|f| f.path.contains("manual")).unwrap();
+        assert!(manual_file
+            .content
+            .starts_with("---\ndescription:\nglobs:\nalwaysApply: false\n---"));
+    }
+
+    #[tokio::test]
+    async fn test_split_sections_match_rules_by_heading() {
+        use crate::types::{
+            CursorAgentConfig, CursorSplitConfig, CursorSplitRule, SectionSplitConfig,
+        };
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("design.md"),
+            "# Design\n\n## Auth\n\nTokens.\n\n## Storage\n\nBuckets.\n",
+        )
+        .await
+        .unwrap();
+        fs::write(docs_path.join("small.md"), "# Small\n\n## Only\n")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.split_sections = Some(SectionSplitConfig {
+            heading_level: None,
+            min_tokens: Some(8),
+        });
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: Some(OutputMode::Split),
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(CursorSplitConfig {
+                rules: vec![CursorSplitRule {
+                    file_patterns: vec!["*-auth*".to_string()],
+                    always_apply: None,
+                    globs: Some(vec!["src/auth/**".to_string()]),
+                    description: None,
+                    manual: None,
+                    sections: None,
+                }],
+            }),
+        });
+
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+        let names: Vec<&str> = files
+            .iter()
+            .map(|f| f.path.rsplit('/').next().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["design-auth.mdc", "design-storage.mdc", "small.mdc"]
+        );
+
+        assert!(files[0].content.contains("globs: src/auth/**"));
+        assert!(files[0].content.ends_with("## Auth\n\nTokens."));
+        assert!(files[1].content.contains("alwaysApply: true"));
+        assert!(files[1].content.ends_with("## Storage\n\nBuckets."));
+    }
+
+    #[tokio::test]
+    async fn test_split_rule_sections_selector() {
+        use crate::types::{CursorAgentConfig, CursorSplitConfig, CursorSplitRule};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("design.md"),
+            "# Design\n\n## API\n\nEndpoints.\n\n## Onboarding\n\nWelcome.\n",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("design-notes.md"),
+            "# Notes\n\nNo API here.\n",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: Some(OutputMode::Split),
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(CursorSplitConfig {
+                rules: vec![CursorSplitRule {
+                    file_patterns: vec!["design*".to_string()],
+                    always_apply: Some(true),
+                    globs: None,
+                    description: None,
+                    manual: None,
+                    sections: Some(vec!["API".to_string()]),
+                }],
+            }),
+        });
+
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+
+        // design-notes.md has no API section and is skipped
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.ends_with("design.mdc"));
+        assert!(files[0].content.ends_with("---\n\n## API\n\nEndpoints."));
+    }
+
+    #[tokio::test]
+    async fn test_split_merges_source_frontmatter_keys() {
+        use crate::types::FrontmatterMode;
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("rust.md"),
+            "---\nglobs: \"**/*.rs\"\nalwaysApply: true\nowner: platform\n---\n# Rust",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.agents.cursor = CursorConfig::Simple(true);
+
+        // Strip (default): only generated keys remain
+        let agent = CursorAgent::new_with_base_dir(
+            config.clone(),
+            temp_dir.path().to_string_lossy().to_string(),
+        );
+        let files = agent.generate().await.unwrap();
+        assert_eq!(
+            files[0].content,
+            "---\ndescription:\nglobs: **/*.rs\nalwaysApply: false\n---\n\n# Rust"
+        );
+
+        // Merge: other keys are appended, the conflicting alwaysApply is dropped
+        config.source_frontmatter = Some(FrontmatterMode::Merge);
+        let agent =
+            CursorAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+        assert_eq!(
+            files[0].content,
+            "---\ndescription:\nglobs: **/*.rs\nalwaysApply: false\nowner: platform\n---\n\n# Rust"
+        );
+    }
 }
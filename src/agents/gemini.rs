@@ -22,11 +22,8 @@ impl GeminiAgent {
 
     /// Generate files for Gemini (merged mode only)
     pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
-        let base_docs_dir = self
-            .config
-            .get_effective_base_docs_dir("gemini")
-            .to_string();
-        let merger = MarkdownMerger::new_with_base_dir(self.config.clone(), base_docs_dir);
+        let merger =
+            MarkdownMerger::new_for_agent(self.config.clone(), "gemini").with_output_dir(".");
         self.generate_merged(&merger).await
     }
 
@@ -58,6 +55,7 @@ mod tests {
             include_filenames: Some(true),         // Enable headers for testing
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
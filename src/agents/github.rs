@@ -9,7 +9,8 @@
  * - Split mode: Generate md files under .github/instructions/
  */
 
-use crate::core::MarkdownMerger;
+use crate::agents::base::BaseAgentUtils;
+use crate::core::{MarkdownMerger, RuleCoverage, SourceDoc};
 use crate::types::{AIContextConfig, GeneratedFile, GitHubSplitRule, OutputMode};
 use anyhow::Result;
 use tokio::fs;
@@ -39,17 +40,44 @@ impl GitHubAgent {
     }
 
     /// Generate files for GitHub Copilot
+    /// Existing output is left untouched; call `clean_output` before writing
     pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
-        let base_docs_dir = self
-            .config
-            .get_effective_base_docs_dir("github")
-            .to_string();
-        let merger = MarkdownMerger::new_with_base_dir(self.config.clone(), base_docs_dir);
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "github");
+
+        match self.config.get_effective_output_mode("github") {
+            OutputMode::Merged => {
+                self.generate_merged(&merger.with_output_dir(self.get_github_dir()))
+                    .await
+            }
+            OutputMode::Split => {
+                self.generate_split(&merger.with_output_dir(self.get_instructions_dir()))
+                    .await
+            }
+        }
+    }
 
+    /// Delete previous output (run after generated content passes all checks)
+    pub async fn clean_output(&self) -> Result<()> {
         match self.config.get_effective_output_mode("github") {
-            OutputMode::Merged => self.generate_merged(&merger).await,
-            OutputMode::Split => self.generate_split(&merger).await,
+            OutputMode::Merged => {
+                // Delete existing *.prompt.md files (for split mode)
+                self.cleanup_split_files().await?;
+
+                // Create .github directory
+                fs::create_dir_all(self.get_github_dir()).await?;
+            }
+            OutputMode::Split => {
+                // Delete existing .github/copilot-instructions.md file (for merged mode)
+                self.cleanup_merged_file().await?;
+
+                // Create .github/instructions directory
+                fs::create_dir_all(self.get_instructions_dir()).await?;
+
+                // Delete existing .instructions.md files
+                self.cleanup_split_files().await?;
+            }
         }
+        Ok(())
     }
 
     /// Merged mode: Generate .github/copilot-instructions.md file
@@ -59,13 +87,6 @@ impl GitHubAgent {
         // GitHub Copilot uses regular Markdown files (no frontmatter)
         let instructions_content = self.create_instructions_content(&content);
 
-        // Delete existing *.prompt.md files (for split mode)
-        self.cleanup_split_files().await?;
-
-        // Create .github directory
-        let github_dir = self.get_github_dir();
-        tokio::fs::create_dir_all(&github_dir).await?;
-
         let output_path = if let Some(base_dir) = &self.base_dir {
             format!("{base_dir}/.github/copilot-instructions.md")
         } else {
@@ -77,34 +98,62 @@ impl GitHubAgent {
 
     /// Split mode: Generate .github/instructions/xxx.instructions.md files
     async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
-        let files = merger.get_individual_files().await?;
-        let mut generated_files = Vec::new();
-
-        // Delete existing .github/copilot-instructions.md file (for merged mode)
-        self.cleanup_merged_file().await?;
-
-        // Create .github/instructions directory
-        let instructions_dir = self.get_instructions_dir();
-        tokio::fs::create_dir_all(&instructions_dir).await?;
-
-        // Delete existing .instructions.md files
-        self.cleanup_split_files().await?;
+        let docs = merger.get_split_documents().await?;
 
         // If split_config is configured, generate according to those rules
-        if let Some(github_config) = &self.config.agents.github.get_advanced_config() {
-            if let Some(split_config) = &github_config.split_config {
-                return self
-                    .generate_split_with_config(&files, &split_config.rules)
-                    .await;
-            }
-        }
+        let rules = self
+            .config
+            .agents
+            .github
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+            .map(|split_config| split_config.rules.as_slice())
+            .unwrap_or_default();
+
+        self.generate_split_with_config(&docs, rules).await
+    }
+
+    /// Generate files according to split_config rules and doc frontmatter
+    async fn generate_split_with_config(
+        &self,
+        docs: &[SourceDoc],
+        rules: &[GitHubSplitRule],
+    ) -> Result<Vec<GeneratedFile>> {
+        let mut generated_files = Vec::new();
 
-        // If split_config is not configured, generate using traditional method
-        for (file_name, content) in files {
-            let instructions_content = self.create_instructions_content(&content);
+        for doc in docs {
+            // First matching rule wins
+            let matched_rule = rules.iter().find(|rule| {
+                rule.file_patterns
+                    .iter()
+                    .any(|pattern| self.file_matches_pattern(&doc.path, pattern))
+            });
+
+            // Frontmatter globs take precedence over the rule's apply_to
+            // Unmatched files are output as-is by default (no apply_to)
+            let apply_to = doc
+                .frontmatter
+                .globs
+                .clone()
+                .or_else(|| matched_rule.and_then(|rule| rule.apply_to.clone()));
+            let generated_keys: &[&str] = match &apply_to {
+                Some(patterns) if !patterns.is_empty() => &["applyTo"],
+                _ => &[],
+            };
+            let source_keys = BaseAgentUtils::source_frontmatter_keys(
+                &self.config,
+                "github",
+                doc,
+                generated_keys,
+            )?;
+            let instructions_content = self.create_instructions_content_with_apply_to(
+                &doc.content,
+                &apply_to,
+                &source_keys,
+            );
 
             // Add .instructions.md by removing extension from filename
-            let base_name = file_name.trim_end_matches(".md");
+            let base_name = self.config.strip_source_extension(&doc.path);
             let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
 
             let output_path = if let Some(base_dir) = &self.base_dir {
@@ -119,70 +168,43 @@ impl GitHubAgent {
         Ok(generated_files)
     }
 
-    /// Generate files according to split_config rules
-    async fn generate_split_with_config(
-        &self,
-        files: &[(String, String)],
-        rules: &[GitHubSplitRule],
-    ) -> Result<Vec<GeneratedFile>> {
-        let mut generated_files = Vec::new();
-        let mut processed_files = std::collections::HashSet::new();
-
-        // Process matching files for each rule
-        for rule in rules {
-            // Collect files that match the rule
-            for (file_name, content) in files {
-                if processed_files.contains(file_name.as_str()) {
-                    continue;
-                }
-
-                // Check if file matches the rule
-                let matches = rule
-                    .file_patterns
-                    .iter()
-                    .any(|pattern| self.file_matches_pattern(file_name, pattern));
-
-                if matches {
-                    // Mark matched file as processed
-                    processed_files.insert(file_name.as_str());
-
-                    // Add applyTo frontmatter
-                    let instructions_content =
-                        self.create_instructions_content_with_apply_to(content, &rule.apply_to);
-
-                    // Preserve original filename
-                    let base_name = file_name.trim_end_matches(".md");
-                    let safe_name = base_name.replace(['/', '\\'], "_");
-
-                    let output_path = if let Some(base_dir) = &self.base_dir {
-                        format!("{base_dir}/.github/instructions/{safe_name}.instructions.md")
-                    } else {
-                        format!(".github/instructions/{safe_name}.instructions.md")
-                    };
-
-                    generated_files.push(GeneratedFile::new(output_path, instructions_content));
-                }
-            }
+    /// Report which split rule each doc matches (None when split rules are not in effect)
+    pub async fn rule_coverage(&self) -> Result<Option<RuleCoverage>> {
+        if self.config.get_effective_output_mode("github") != OutputMode::Split {
+            return Ok(None);
         }
+        let Some(split_config) = self
+            .config
+            .agents
+            .github
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+        else {
+            return Ok(None);
+        };
 
-        // Output unmatched files as-is by default (no apply_to)
-        for (file_name, content) in files {
-            if !processed_files.contains(file_name.as_str()) {
-                let instructions_content = self.create_instructions_content(content);
-                let base_name = file_name.trim_end_matches(".md");
-                let safe_name = base_name.replace(['/', '\\'], "_");
-
-                let output_path = if let Some(base_dir) = &self.base_dir {
-                    format!("{base_dir}/.github/instructions/{safe_name}.instructions.md")
-                } else {
-                    format!(".github/instructions/{safe_name}.instructions.md")
-                };
-
-                generated_files.push(GeneratedFile::new(output_path, instructions_content));
-            }
-        }
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "github");
+        let docs = merger.get_split_documents().await?;
+        let doc_paths: Vec<String> = docs.iter().map(|doc| doc.path.clone()).collect();
+        let patterns: Vec<&[String]> = split_config
+            .rules
+            .iter()
+            .map(|rule| rule.file_patterns.as_slice())
+            .collect();
 
-        Ok(generated_files)
+        let mut coverage =
+            RuleCoverage::build("github", &doc_paths, &patterns, |file_name, patterns| {
+                Ok(patterns
+                    .iter()
+                    .any(|pattern| self.file_matches_pattern(file_name, pattern)))
+            })?;
+        let frontmatter_docs: Vec<String> = docs
+            .iter()
+            .filter(|doc| doc.frontmatter.globs.is_some())
+            .map(|doc| doc.path.clone())
+            .collect();
+        coverage.mark_frontmatter_docs(&frontmatter_docs);
+        Ok(Some(coverage))
     }
 
     /// Check if filename matches pattern
@@ -214,17 +236,26 @@ impl GitHubAgent {
     }
 
     /// Create GitHub Copilot content with applyTo frontmatter
+    /// `source_keys` are merged source doc frontmatter lines appended after applyTo
     fn create_instructions_content_with_apply_to(
         &self,
         content: &str,
         apply_to: &Option<Vec<String>>,
+        source_keys: &str,
     ) -> String {
-        match apply_to {
+        let mut frontmatter = match apply_to {
             Some(patterns) if !patterns.is_empty() => {
                 let apply_to_value = patterns.join(",");
-                format!("---\napplyTo: \"{apply_to_value}\"\n---\n\n{content}")
+                format!("applyTo: \"{apply_to_value}\"\n")
             }
-            _ => content.to_string(),
+            _ => String::new(),
+        };
+        frontmatter.push_str(source_keys);
+
+        if frontmatter.is_empty() {
+            content.to_string()
+        } else {
+            format!("---\n{frontmatter}---\n\n{content}")
         }
     }
 
@@ -307,6 +338,7 @@ mod tests {
             include_filenames: Some(true), // Enable headers for testing
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
@@ -495,6 +527,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(GitHubSplitConfig {
                 rules: vec![
                     GitHubSplitRule {
@@ -571,6 +609,7 @@ mod tests {
         let content_with_apply_to = agent.create_instructions_content_with_apply_to(
             "Test content",
             &Some(vec!["**/*.ts".to_string(), "**/*.tsx".to_string()]),
+            "",
         );
         assert!(content_with_apply_to.starts_with("---"));
         assert!(content_with_apply_to.contains("applyTo: \"**/*.ts,**/*.tsx\""));
@@ -578,12 +617,12 @@ mod tests {
 
         // applyTo is not set
         let content_without_apply_to =
-            agent.create_instructions_content_with_apply_to("Test content", &None);
+            agent.create_instructions_content_with_apply_to("Test content", &None, "");
         assert_eq!(content_without_apply_to, "Test content");
 
         // applyTo is empty array
         let content_empty_apply_to =
-            agent.create_instructions_content_with_apply_to("Test content", &Some(vec![]));
+            agent.create_instructions_content_with_apply_to("Test content", &Some(vec![]), "");
         assert_eq!(content_empty_apply_to, "Test content");
     }
 
@@ -616,6 +655,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(GitHubSplitConfig {
                 rules: vec![
                     GitHubSplitRule {
@@ -683,4 +728,83 @@ mod tests {
         assert!(!security_file.content.contains("applyTo:"));
         assert!(security_file.content.contains("# Security"));
     }
+
+    #[tokio::test]
+    async fn test_rule_coverage() {
+        use crate::types::{GitHubAgentConfig, GitHubConfig, GitHubSplitConfig};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("backend.md"), "Backend")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("general.md"), "General")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        config.agents.github = GitHubConfig::Advanced(GitHubAgentConfig {
+            enabled: true,
+            output_mode: Some(OutputMode::Split),
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(GitHubSplitConfig {
+                rules: vec![
+                    GitHubSplitRule {
+                        file_patterns: vec!["*backend*".to_string()],
+                        apply_to: Some(vec!["**/*.rs".to_string()]),
+                    },
+                    GitHubSplitRule {
+                        file_patterns: vec!["*frontend*".to_string()],
+                        apply_to: Some(vec!["**/*.ts".to_string()]),
+                    },
+                ],
+            }),
+        });
+
+        let agent = GitHubAgent::new(config);
+        let coverage = agent.rule_coverage().await.unwrap().unwrap();
+
+        assert_eq!(coverage.rules[0].matched_docs, vec!["backend.md"]);
+        assert!(coverage.rules[1].matched_docs.is_empty());
+        assert_eq!(coverage.fallback_docs, vec!["general.md"]);
+        assert_eq!(coverage.warnings().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_generate_split_with_doc_frontmatter_globs() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("frontend.md"),
+            "---\nglobs: [\"**/*.ts\", \"**/*.tsx\"]\n---\n# Frontend",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("claude-only.md"),
+            "---\nagents: [claude]\n---\n# Claude",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy(), OutputMode::Split);
+        let agent =
+            GitHubAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        let files = agent.generate().await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].content,
+            "---\napplyTo: \"**/*.ts,**/*.tsx\"\n---\n\n# Frontend"
+        );
+    }
 }
@@ -5,10 +5,10 @@
  * Outputs files to .kiro/steering/ directory (supports split mode only)
  */
 
-use crate::core::MarkdownMerger;
+use crate::agents::base::BaseAgentUtils;
+use crate::core::{matches_any_pattern, DocFrontmatter, MarkdownMerger, RuleCoverage};
 use crate::types::{AIContextConfig, GeneratedFile, InclusionMode, KiroInclusionRule};
 use anyhow::Result;
-use glob::Pattern;
 
 /// Kiro agent
 pub struct KiroAgent {
@@ -23,29 +23,44 @@ impl KiroAgent {
 
     /// Generate files for Kiro (split mode only)
     pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
-        let base_docs_dir = self.config.get_effective_base_docs_dir("kiro").to_string();
-        let merger = MarkdownMerger::new_with_base_dir(self.config.clone(), base_docs_dir);
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "kiro")
+            .with_output_dir(".kiro/steering");
         self.generate_split(&merger).await
     }
 
     /// Split mode: generate individual files in .kiro/steering/ directory
     async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
-        let files = merger.get_individual_files().await?;
+        let docs = merger.get_split_documents().await?;
         let mut generated_files = Vec::new();
 
         // Get Kiro split config rules if available
-        let rules = self.get_inclusion_rules();
-
-        for (file_name, content) in files {
-            let sanitized_name = self.sanitize_filename(&file_name);
+        let rules = self
+            .get_inclusion_rules()
+            .map(|rules| rules.as_slice())
+            .unwrap_or_default();
+
+        for doc in docs {
+            // Steering files are always .md, whichever source extension matched
+            let sanitized_name = format!(
+                "{}.md",
+                self.sanitize_filename(self.config.strip_source_extension(&doc.path))
+            );
             let output_path = self.get_split_output_path(&sanitized_name);
 
-            // Generate content with YAML frontmatter if rules are defined
-            let final_content = if let Some(rules) = &rules {
-                self.add_yaml_frontmatter(&file_name, content, rules)?
-            } else {
-                content
-            };
+            // Generate content with YAML frontmatter if a rule or doc frontmatter applies
+            let source_keys = BaseAgentUtils::source_frontmatter_keys(
+                &self.config,
+                "kiro",
+                &doc,
+                &["inclusion", "fileMatchPattern"],
+            )?;
+            let final_content = self.add_yaml_frontmatter(
+                &doc.path,
+                doc.content,
+                &doc.frontmatter,
+                &source_keys,
+                rules,
+            )?;
 
             generated_files.push(GeneratedFile::new(output_path, final_content));
         }
@@ -53,6 +68,33 @@ impl KiroAgent {
         Ok(generated_files)
     }
 
+    /// Report which inclusion rule each doc matches (None when no rules are configured)
+    pub async fn rule_coverage(&self) -> Result<Option<RuleCoverage>> {
+        let Some(rules) = self.get_inclusion_rules() else {
+            return Ok(None);
+        };
+
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "kiro");
+        let docs = merger.get_split_documents().await?;
+        let doc_paths: Vec<String> = docs.iter().map(|doc| doc.path.clone()).collect();
+        let patterns: Vec<&[String]> = rules
+            .iter()
+            .map(|rule| rule.file_patterns.as_slice())
+            .collect();
+
+        let mut coverage =
+            RuleCoverage::build("kiro", &doc_paths, &patterns, |file_name, patterns| {
+                matches_any_pattern(file_name, patterns)
+            })?;
+        let frontmatter_docs: Vec<String> = docs
+            .iter()
+            .filter(|doc| doc.frontmatter.kiro_inclusion.is_some())
+            .map(|doc| doc.path.clone())
+            .collect();
+        coverage.mark_frontmatter_docs(&frontmatter_docs);
+        Ok(Some(coverage))
+    }
+
     /// Get split mode output path (.kiro/steering/{filename})
     fn get_split_output_path(&self, filename: &str) -> String {
         format!(".kiro/steering/{filename}")
@@ -75,52 +117,82 @@ impl KiroAgent {
     }
 
     /// Add YAML frontmatter based on matching rules
+    /// Doc frontmatter (kiro_inclusion, globs) takes precedence over the matching rule
+    /// `source_keys` are merged source doc frontmatter lines appended to the header
     fn add_yaml_frontmatter(
         &self,
         file_name: &str,
         content: String,
+        frontmatter: &DocFrontmatter,
+        source_keys: &str,
         rules: &[KiroInclusionRule],
     ) -> Result<String> {
         // Find the first matching rule
+        let mut matched_rule = None;
         for rule in rules {
-            if self.matches_any_pattern(file_name, &rule.file_patterns)? {
-                let yaml_header = self.create_yaml_header(rule)?;
-                return Ok(format!("{yaml_header}\n{content}"));
+            if matches_any_pattern(file_name, &rule.file_patterns)? {
+                matched_rule = Some(rule);
+                break;
             }
         }
 
-        // No matching rule found, return content as-is
-        Ok(content)
-    }
-
-    /// Check if filename matches any of the given patterns
-    fn matches_any_pattern(&self, file_name: &str, patterns: &[String]) -> Result<bool> {
-        for pattern_str in patterns {
-            let pattern = Pattern::new(pattern_str)?;
-            if pattern.matches(file_name) {
-                return Ok(true);
+        let inclusion = frontmatter
+            .kiro_inclusion
+            .as_ref()
+            .or(matched_rule.map(|rule| &rule.inclusion));
+
+        let mut yaml_header = match inclusion {
+            Some(inclusion) => {
+                let match_patterns = match &frontmatter.globs {
+                    Some(globs) => globs.clone(),
+                    None => matched_rule
+                        .and_then(|rule| rule.match_pattern.clone())
+                        .into_iter()
+                        .collect(),
+                };
+                self.create_yaml_header(inclusion, &match_patterns)?
             }
+            None => String::new(),
+        };
+        yaml_header.push_str(source_keys);
+
+        if yaml_header.is_empty() {
+            // No matching rule found, return content as-is
+            return Ok(content);
         }
-        Ok(false)
+        Ok(format!("---\n{yaml_header}---\n{content}"))
     }
 
-    /// Create YAML frontmatter header based on inclusion rule
-    fn create_yaml_header(&self, rule: &KiroInclusionRule) -> Result<String> {
-        match rule.inclusion {
-            InclusionMode::Always => Ok("---\ninclusion: always\n---".to_string()),
-            InclusionMode::FileMatch => {
-                if let Some(match_pattern) = &rule.match_pattern {
-                    Ok(format!(
-                        "---\ninclusion: fileMatch\nfileMatchPattern: \"{match_pattern}\"\n---"
-                    ))
-                } else {
+    /// Create YAML frontmatter header lines based on inclusion mode
+    fn create_yaml_header(
+        &self,
+        inclusion: &InclusionMode,
+        match_patterns: &[String],
+    ) -> Result<String> {
+        match inclusion {
+            InclusionMode::Always => Ok("inclusion: always\n".to_string()),
+            InclusionMode::FileMatch => match match_patterns {
+                [] => {
                     // fileMatch mode requires match_pattern
                     anyhow::bail!(
                         "fileMatch inclusion mode requires match_pattern to be specified"
                     );
                 }
-            }
-            InclusionMode::Manual => Ok("---\ninclusion: manual\n---".to_string()),
+                [match_pattern] => Ok(format!(
+                    "inclusion: fileMatch\nfileMatchPattern: \"{match_pattern}\"\n"
+                )),
+                patterns => {
+                    let pattern_list = patterns
+                        .iter()
+                        .map(|p| format!("\"{p}\""))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    Ok(format!(
+                        "inclusion: fileMatch\nfileMatchPattern: [{pattern_list}]\n"
+                    ))
+                }
+            },
+            InclusionMode::Manual => Ok("inclusion: manual\n".to_string()),
         }
     }
 }
@@ -139,6 +211,7 @@ mod tests {
             include_filenames: Some(false),       // Default is false
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
@@ -232,6 +305,24 @@ mod tests {
         assert_eq!(files[0].content, "Nested content");
     }
 
+    #[tokio::test]
+    async fn test_generate_with_markdown_extension() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("intro.markdown"), "Intro")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.source_extensions = Some(vec!["md".to_string(), "markdown".to_string()]);
+        let agent = KiroAgent::new(config);
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, ".kiro/steering/intro.md");
+    }
+
     #[tokio::test]
     async fn test_sanitize_filename() {
         let config = create_test_config("./docs");
@@ -370,6 +461,12 @@ mod tests {
             output_mode: None,
             include_filenames: None,
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(KiroSplitConfig {
                 rules: vec![KiroInclusionRule {
                     file_patterns: vec!["*project*".to_string()],
@@ -412,6 +509,12 @@ mod tests {
             output_mode: None,
             include_filenames: None,
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(KiroSplitConfig {
                 rules: vec![
                     KiroInclusionRule {
@@ -467,6 +570,12 @@ mod tests {
             output_mode: None,
             include_filenames: None,
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(KiroSplitConfig {
                 rules: vec![KiroInclusionRule {
                     file_patterns: vec!["*readme*".to_string(), "*project*".to_string()],
@@ -505,6 +614,12 @@ mod tests {
             output_mode: None,
             include_filenames: None,
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
 
@@ -536,6 +651,12 @@ mod tests {
             output_mode: None,
             include_filenames: None,
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: Some(KiroSplitConfig {
                 rules: vec![
                     KiroInclusionRule {
@@ -560,4 +681,146 @@ mod tests {
         // First matching rule should apply (always)
         assert!(files[0].content.starts_with("---\ninclusion: always\n---"));
     }
+
+    #[tokio::test]
+    async fn test_rule_coverage() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("project.md"), "Project")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("notes.md"), "Notes")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.agents.kiro = KiroConfig::Advanced(KiroAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(KiroSplitConfig {
+                rules: vec![
+                    KiroInclusionRule {
+                        file_patterns: vec!["*project*".to_string()],
+                        inclusion: InclusionMode::Always,
+                        match_pattern: None,
+                    },
+                    KiroInclusionRule {
+                        file_patterns: vec!["project.md".to_string()],
+                        inclusion: InclusionMode::Manual,
+                        match_pattern: None,
+                    },
+                ],
+            }),
+        });
+
+        let agent = KiroAgent::new(config);
+        let coverage = agent.rule_coverage().await.unwrap().unwrap();
+
+        assert_eq!(coverage.rules[0].matched_docs, vec!["project.md"]);
+        assert_eq!(coverage.rules[1].shadowed_docs, vec!["project.md"]);
+        assert_eq!(coverage.fallback_docs, vec!["notes.md"]);
+
+        let agent = KiroAgent::new(create_test_config(&docs_path.to_string_lossy()));
+        assert!(agent.rule_coverage().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_doc_frontmatter() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("rust.md"),
+            "---\nkiro_inclusion: fileMatch\nglobs: [\"**/*.rs\", \"Cargo.toml\"]\n---\n# Rust",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("project.md"),
+            "---\nkiro_inclusion: manual\n---\n# Project",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.agents.kiro = KiroConfig::Advanced(KiroAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(KiroSplitConfig {
+                rules: vec![KiroInclusionRule {
+                    file_patterns: vec!["*project*".to_string()],
+                    inclusion: InclusionMode::Always,
+                    match_pattern: None,
+                }],
+            }),
+        });
+
+        let agent = KiroAgent::new(config);
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        let project = files.iter().find(|f| f.path.contains("project")).unwrap();
+        assert_eq!(project.content, "---\ninclusion: manual\n---\n# Project");
+
+        let rust = files.iter().find(|f| f.path.contains("rust")).unwrap();
+        assert_eq!(
+            rust.content,
+            "---\ninclusion: fileMatch\nfileMatchPattern: [\"**/*.rs\", \"Cargo.toml\"]\n---\n# Rust"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_merges_source_frontmatter_keys() {
+        use crate::types::FrontmatterMode;
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("project.md"),
+            "---\nkiro_inclusion: always\ninclusion: manual\ntitle: Project\n---\n# Project",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("notes.md"),
+            "---\ntitle: Notes\n---\n# Notes",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.source_frontmatter = Some(FrontmatterMode::Merge);
+
+        let agent = KiroAgent::new(config);
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        let project = files.iter().find(|f| f.path.contains("project")).unwrap();
+        assert_eq!(
+            project.content,
+            "---\ninclusion: always\ntitle: Project\n---\n# Project"
+        );
+
+        // Docs without inclusion still carry their merged keys
+        let notes = files.iter().find(|f| f.path.contains("notes")).unwrap();
+        assert_eq!(notes.content, "---\ntitle: Notes\n---\n# Notes");
+    }
 }
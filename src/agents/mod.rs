@@ -4,19 +4,27 @@
  * This module provides implementations for each AI agent.
  */
 
+pub mod aider;
 pub mod base;
 pub mod claude;
 pub mod cline;
 pub mod codex;
+pub mod continue_dev;
 pub mod cursor;
 pub mod gemini;
 pub mod github;
 pub mod kiro;
+pub mod roo;
+pub mod windsurf;
 
+pub use aider::*;
 pub use claude::*;
 pub use cline::*;
 pub use codex::*;
+pub use continue_dev::*;
 pub use cursor::*;
 pub use gemini::*;
 pub use github::*;
 pub use kiro::*;
+pub use roo::*;
+pub use windsurf::*;
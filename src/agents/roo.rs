@@ -0,0 +1,400 @@
+/*!
+ * AI Context Management Tool - Roo Code Agent
+ *
+ * Roo Code agent implementation
+ * Specification: https://docs.roocode.com/features/custom-instructions
+ *
+ * Split mode: .md files in .roo/rules/ (all modes) or .roo/rules-{mode}/ (one mode)
+ * Merged mode: Single .roorules file (Roo's fallback when .roo/rules/ is missing)
+ * Generated paths are recorded in a manifest so cleanup never touches hand-written rules
+ */
+
+use crate::core::{matches_any_pattern, MarkdownMerger, RuleCoverage};
+use crate::types::{AIContextConfig, GeneratedFile, OutputMode, RooSplitRule};
+use anyhow::Result;
+use std::path::{Component, Path};
+use tokio::fs;
+
+/// Manifest of files written by the previous `aicm generate`
+const MANIFEST_FILE: &str = ".roo/.aicm-generated";
+
+/// Roo Code agent
+pub struct RooAgent {
+    config: AIContextConfig,
+    base_dir: Option<String>,
+}
+
+impl RooAgent {
+    /// Create a new Roo Code agent
+    pub fn new(config: AIContextConfig) -> Self {
+        Self {
+            config,
+            base_dir: None,
+        }
+    }
+
+    /// Create Roo Code agent with specified base directory
+    #[cfg(test)]
+    pub fn new_with_base_dir(config: AIContextConfig, base_dir: String) -> Self {
+        Self {
+            config,
+            base_dir: Some(base_dir),
+        }
+    }
+
+    /// Generate files for Roo Code
+    /// Existing output is left untouched; call `clean_output` before writing
+    pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "roo");
+
+        // Rule files as (path relative to the project root, content)
+        let rule_files = match self.config.get_effective_output_mode("roo") {
+            OutputMode::Merged => {
+                let output_dir = self.base_dir.clone().unwrap_or_else(|| ".".to_string());
+                self.generate_merged(&merger.with_output_dir(output_dir))
+                    .await?
+            }
+            OutputMode::Split => {
+                // .roo/rules/ and .roo/rules-{mode}/ sit at the same depth, so links resolve alike
+                let output_dir = self.output_path(".roo/rules");
+                self.generate_split(&merger.with_output_dir(output_dir))
+                    .await?
+            }
+        };
+
+        let mut manifest =
+            "# Files generated by aicm for Roo Code (removed on the next aicm generate)\n"
+                .to_string();
+        let mut generated_files = Vec::new();
+        for (path, content) in rule_files {
+            manifest.push_str(&format!("{path}\n"));
+            generated_files.push(GeneratedFile::new(self.output_path(&path), content));
+        }
+        generated_files.push(GeneratedFile::support(
+            self.output_path(MANIFEST_FILE),
+            manifest,
+        ));
+
+        Ok(generated_files)
+    }
+
+    /// Merged mode: Single .roorules file
+    async fn generate_merged(&self, merger: &MarkdownMerger) -> Result<Vec<(String, String)>> {
+        let content = merger.merge_all_with_options(Some("roo")).await?;
+        Ok(vec![(".roorules".to_string(), content)])
+    }
+
+    /// Split mode: Docs go to the rules directory of the first matching rule's mode
+    async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<(String, String)>> {
+        let rules = self
+            .get_split_rules()
+            .map(|rules| rules.as_slice())
+            .unwrap_or_default();
+        let mut rule_files = Vec::new();
+
+        let docs = merger.get_split_documents().await?;
+        for doc in docs {
+            let mut mode = None;
+            for rule in rules {
+                if matches_any_pattern(&doc.path, &rule.file_patterns)? {
+                    mode = rule.mode.as_deref();
+                    break;
+                }
+            }
+
+            let rules_dir = rules_dir_for_mode(mode)?;
+            let base_name = self.config.strip_source_extension(&doc.path);
+            let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
+            rule_files.push((format!("{rules_dir}/{safe_name}.md"), doc.content));
+        }
+
+        Ok(rule_files)
+    }
+
+    /// Report which split rule each doc matches (None when split rules are not in effect)
+    pub async fn rule_coverage(&self) -> Result<Option<RuleCoverage>> {
+        if self.config.get_effective_output_mode("roo") != OutputMode::Split {
+            return Ok(None);
+        }
+        let Some(rules) = self.get_split_rules() else {
+            return Ok(None);
+        };
+
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "roo");
+        let docs = merger.get_split_documents().await?;
+        let doc_paths: Vec<String> = docs.iter().map(|doc| doc.path.clone()).collect();
+        let patterns: Vec<&[String]> = rules
+            .iter()
+            .map(|rule| rule.file_patterns.as_slice())
+            .collect();
+
+        let coverage = RuleCoverage::build("roo", &doc_paths, &patterns, |file_name, patterns| {
+            matches_any_pattern(file_name, patterns)
+        })?;
+        Ok(Some(coverage))
+    }
+
+    /// Delete files listed in the previous manifest (rule directories left empty are removed)
+    /// Run after generated content passes all checks
+    pub async fn clean_output(&self) -> Result<()> {
+        let Ok(manifest) = fs::read_to_string(self.output_path(MANIFEST_FILE)).await else {
+            return Ok(());
+        };
+
+        for path in manifest_entries(&manifest) {
+            let output_path = self.output_path(path);
+            if !fs::metadata(&output_path)
+                .await
+                .is_ok_and(|metadata| metadata.is_file())
+            {
+                continue;
+            }
+            fs::remove_file(&output_path).await?;
+            if path.starts_with(".roo/") {
+                if let Some(parent) = Path::new(&output_path).parent() {
+                    // Fails while the directory still holds other files
+                    let _ = fs::remove_dir(parent).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get split rules from Roo configuration
+    fn get_split_rules(&self) -> Option<&Vec<RooSplitRule>> {
+        self.config
+            .agents
+            .roo
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+            .map(|split_config| &split_config.rules)
+    }
+
+    /// Get output path of a project-relative path
+    fn output_path(&self, path: &str) -> String {
+        if let Some(base_dir) = &self.base_dir {
+            format!("{base_dir}/{path}")
+        } else {
+            path.to_string()
+        }
+    }
+}
+
+/// Get rules directory for a mode slug (None: rules for all modes)
+fn rules_dir_for_mode(mode: Option<&str>) -> Result<String> {
+    let Some(mode) = mode else {
+        return Ok(".roo/rules".to_string());
+    };
+    let valid = !mode.is_empty()
+        && mode
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        anyhow::bail!(
+            "Invalid Roo mode '{mode}': use a mode slug of lowercase letters, digits and hyphens"
+        );
+    }
+    Ok(format!(".roo/rules-{mode}"))
+}
+
+/// Paths in a manifest that point at Roo rule output (others are ignored)
+fn manifest_entries(manifest: &str) -> impl Iterator<Item = &str> {
+    manifest
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| {
+            let path = Path::new(line);
+            let is_relative = path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+            is_relative && (*line == ".roorules" || line.starts_with(".roo/rules"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AgentConfig, RooAgentConfig, RooConfig, RooSplitConfig};
+    use tempfile::tempdir;
+    use tokio::fs;
+
+    fn create_test_config(base_dir: &str, output_mode: OutputMode) -> AIContextConfig {
+        AIContextConfig {
+            version: "1.0".to_string(),
+            output_mode: Some(output_mode),
+            include_filenames: Some(false),
+            base_docs_dir: base_dir.to_string(),
+            agents: AgentConfig::default(),
+            ..Default::default()
+        }
+    }
+
+    fn with_rules(mut config: AIContextConfig, rules: &[(&str, Option<&str>)]) -> AIContextConfig {
+        let rules = rules
+            .iter()
+            .map(|(pattern, mode)| RooSplitRule {
+                file_patterns: vec![pattern.to_string()],
+                mode: mode.map(str::to_string),
+            })
+            .collect();
+        config.agents.roo = RooConfig::Advanced(RooAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(RooSplitConfig { rules }),
+        });
+        config
+    }
+
+    async fn write_outputs(files: &[GeneratedFile]) {
+        for file in files {
+            let path = Path::new(&file.path);
+            fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+            fs::write(path, &file.content).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_merged() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style\nUse tabs.")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Merged);
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = RooAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, format!("{base_dir}/.roorules"));
+        assert!(files[0].content.contains("Use tabs."));
+        assert_eq!(files[1].path, format!("{base_dir}/.roo/.aicm-generated"));
+        assert!(files[1].content.ends_with("\n.roorules\n"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_split_by_mode() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("api")).await.unwrap();
+        fs::write(docs_dir.join("api/errors.md"), "# Errors")
+            .await
+            .unwrap();
+        fs::write(docs_dir.join("design.md"), "# Design")
+            .await
+            .unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style")
+            .await
+            .unwrap();
+
+        let config = with_rules(
+            create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split),
+            &[("design*", Some("architect")), ("api/*", Some("debug"))],
+        );
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = RooAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        let paths: Vec<String> = files
+            .iter()
+            .map(|file| file.path.replace(&format!("{base_dir}/"), ""))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".roo/rules-debug/api_errors.md",
+                ".roo/rules-architect/design.md",
+                ".roo/rules/style.md",
+                ".roo/.aicm-generated",
+            ]
+        );
+        assert_eq!(files[0].content, "# Errors");
+        assert!(files.last().unwrap().support);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_removes_only_generated_files() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("design.md"), "# Design")
+            .await
+            .unwrap();
+        fs::write(docs_dir.join("style.md"), "# Style")
+            .await
+            .unwrap();
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+
+        // First run puts design.md into the architect mode
+        let config = with_rules(
+            create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split),
+            &[("design*", Some("architect"))],
+        );
+        let files = RooAgent::new_with_base_dir(config, base_dir.clone())
+            .generate()
+            .await
+            .unwrap();
+        write_outputs(&files).await;
+        let hand_written = temp_dir.path().join(".roo/rules/team.md");
+        fs::write(&hand_written, "# Team").await.unwrap();
+
+        // Second run without rules moves every doc to .roo/rules/
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split);
+        let agent = RooAgent::new_with_base_dir(config, base_dir.clone());
+        let files = agent.generate().await.unwrap();
+        agent.clean_output().await.unwrap();
+
+        assert!(!temp_dir.path().join(".roo/rules-architect").exists());
+        assert!(!temp_dir.path().join(".roo/rules/style.md").exists());
+        assert!(hand_written.exists());
+        assert!(files[2].content.contains(".roo/rules/design.md\n"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_keeps_previous_output() {
+        let temp_dir = tempdir().unwrap();
+        let roorules = temp_dir.path().join(".roorules");
+        fs::create_dir_all(temp_dir.path().join(".roo"))
+            .await
+            .unwrap();
+        fs::write(&roorules, "old").await.unwrap();
+        fs::write(temp_dir.path().join(".roo/.aicm-generated"), ".roorules\n")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Split);
+        let agent =
+            RooAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        agent.generate().await.unwrap();
+        assert!(roorules.exists());
+
+        agent.clean_output().await.unwrap();
+        assert!(!roorules.exists());
+    }
+
+    #[test]
+    fn test_rules_dir_for_mode() {
+        assert_eq!(rules_dir_for_mode(None).unwrap(), ".roo/rules");
+        assert_eq!(rules_dir_for_mode(Some("code")).unwrap(), ".roo/rules-code");
+        assert!(rules_dir_for_mode(Some("../code")).is_err());
+        assert!(rules_dir_for_mode(Some("")).is_err());
+    }
+
+    #[test]
+    fn test_manifest_entries_ignore_other_paths() {
+        let manifest = "# header\n.roorules\n.roo/rules/a.md\n../secret.md\n/etc/passwd\nsrc/main.rs\n.roo/rules/../../x.md\n";
+        let entries: Vec<&str> = manifest_entries(manifest).collect();
+        assert_eq!(entries, vec![".roorules", ".roo/rules/a.md"]);
+    }
+}
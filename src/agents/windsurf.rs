@@ -0,0 +1,555 @@
+/*!
+ * AI Context Management Tool - Windsurf Agent
+ *
+ * Windsurf agent implementation
+ * Specification: https://docs.windsurf.com/windsurf/cascade/memories#rules
+ *
+ * Split mode: Multiple .md files with trigger frontmatter in .windsurf/rules/
+ * Merged mode: Single legacy .windsurfrules file
+ */
+
+use crate::agents::base::BaseAgentUtils;
+use crate::core::{matches_any_pattern, DocFrontmatter, MarkdownMerger, RuleCoverage};
+use crate::types::{
+    AIContextConfig, GeneratedFile, OutputMode, WindsurfSplitRule, WindsurfTrigger,
+};
+use anyhow::Result;
+use tokio::fs;
+
+/// Characters Windsurf reads from each file in .windsurf/rules/
+const RULE_FILE_CHAR_LIMIT: usize = 12_000;
+/// Characters Windsurf reads from the legacy .windsurfrules file
+const LEGACY_RULES_CHAR_LIMIT: usize = 6_000;
+
+/// Windsurf agent
+pub struct WindsurfAgent {
+    config: AIContextConfig,
+    base_dir: Option<String>,
+}
+
+impl WindsurfAgent {
+    /// Create a new Windsurf agent
+    pub fn new(config: AIContextConfig) -> Self {
+        Self {
+            config,
+            base_dir: None,
+        }
+    }
+
+    /// Create Windsurf agent with specified base directory
+    #[cfg(test)]
+    pub fn new_with_base_dir(config: AIContextConfig, base_dir: String) -> Self {
+        Self {
+            config,
+            base_dir: Some(base_dir),
+        }
+    }
+
+    /// Generate files for Windsurf
+    /// Existing output is left untouched; call `clean_output` before writing
+    pub async fn generate(&self) -> Result<Vec<GeneratedFile>> {
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "windsurf");
+
+        let files = match self.config.get_effective_output_mode("windsurf") {
+            OutputMode::Merged => {
+                let output_dir = self.base_dir.clone().unwrap_or_else(|| ".".to_string());
+                self.generate_merged(&merger.with_output_dir(output_dir))
+                    .await?
+            }
+            OutputMode::Split => {
+                self.generate_split(&merger.with_output_dir(self.get_rules_dir()))
+                    .await?
+            }
+        };
+
+        for warning in self.char_limit_warnings(&files) {
+            eprintln!("Warning: {warning}");
+        }
+        Ok(files)
+    }
+
+    /// Delete previous output (run after generated content passes all checks)
+    pub async fn clean_output(&self) -> Result<()> {
+        match self.config.get_effective_output_mode("windsurf") {
+            // Delete split mode rules, which Windsurf would load alongside .windsurfrules
+            OutputMode::Merged => self.remove_rule_files(&self.get_rules_dir()).await,
+            OutputMode::Split => {
+                // Delete the merged mode .windsurfrules file if it exists
+                let merged_path = self.get_merged_output_path();
+                if let Ok(metadata) = fs::metadata(&merged_path).await {
+                    if metadata.is_file() {
+                        fs::remove_file(&merged_path).await?;
+                    }
+                }
+                // Prepare .windsurf/rules/ directory
+                self.prepare_rules_directory(&self.get_rules_dir()).await
+            }
+        }
+    }
+
+    /// Merged mode: Single .windsurfrules file
+    async fn generate_merged(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
+        let content = merger.merge_all_with_options(Some("windsurf")).await?;
+        Ok(vec![GeneratedFile::new(
+            self.get_merged_output_path(),
+            content,
+        )])
+    }
+
+    /// Split mode: Multiple .md files in .windsurf/rules/ folder
+    async fn generate_split(&self, merger: &MarkdownMerger) -> Result<Vec<GeneratedFile>> {
+        let docs = merger.get_split_documents().await?;
+        let mut generated_files = Vec::new();
+        let rules_dir = self.get_rules_dir();
+
+        let rules = self
+            .get_split_rules()
+            .map(|rules| rules.as_slice())
+            .unwrap_or_default();
+
+        for doc in docs {
+            // First matching rule wins
+            let mut matched_rule = None;
+            for rule in rules {
+                if matches_any_pattern(&doc.path, &rule.file_patterns)? {
+                    matched_rule = Some(rule);
+                    break;
+                }
+            }
+
+            let rule = self.resolve_rule(matched_rule, &doc.frontmatter);
+            let mut frontmatter = self.create_frontmatter(&rule)?;
+            frontmatter.push_str(&BaseAgentUtils::source_frontmatter_keys(
+                &self.config,
+                "windsurf",
+                &doc,
+                &["trigger", "description", "globs"],
+            )?);
+
+            let base_name = self.config.strip_source_extension(&doc.path);
+            let safe_name = base_name.replace(['/', '\\'], "_"); // Convert path separators to underscores
+
+            generated_files.push(GeneratedFile::new(
+                format!("{rules_dir}/{safe_name}.md"),
+                format!("---\n{frontmatter}---\n\n{}", doc.content),
+            ));
+        }
+
+        Ok(generated_files)
+    }
+
+    /// Resolve effective rule for a doc
+    /// Frontmatter values take precedence; the matching split_config rule provides defaults
+    fn resolve_rule(
+        &self,
+        matched_rule: Option<&WindsurfSplitRule>,
+        frontmatter: &DocFrontmatter,
+    ) -> WindsurfSplitRule {
+        let mut rule = matched_rule.cloned().unwrap_or(WindsurfSplitRule {
+            file_patterns: Vec::new(),
+            trigger: None,
+            globs: None,
+            description: None,
+        });
+        if !frontmatter.has_rule_metadata() {
+            return rule;
+        }
+
+        if frontmatter.globs.is_some() {
+            rule.globs = frontmatter.globs.clone();
+        }
+        if frontmatter.description.is_some() {
+            rule.description = frontmatter.description.clone();
+        }
+        // Frontmatter metadata decides the trigger again
+        rule.trigger = match frontmatter.always_apply {
+            Some(true) => Some(WindsurfTrigger::AlwaysOn),
+            Some(false) if frontmatter.globs.is_none() && frontmatter.description.is_none() => {
+                Some(WindsurfTrigger::Manual)
+            }
+            _ => None,
+        };
+        rule
+    }
+
+    /// Create frontmatter lines for a rule
+    /// Without an explicit trigger: glob with globs, model_decision with description, else always_on
+    fn create_frontmatter(&self, rule: &WindsurfSplitRule) -> Result<String> {
+        let trigger = rule
+            .trigger
+            .unwrap_or(match (&rule.globs, &rule.description) {
+                (Some(_), _) => WindsurfTrigger::Glob,
+                (None, Some(_)) => WindsurfTrigger::ModelDecision,
+                (None, None) => WindsurfTrigger::AlwaysOn,
+            });
+
+        match trigger {
+            WindsurfTrigger::AlwaysOn => Ok("trigger: always_on\n".to_string()),
+            WindsurfTrigger::Glob => match rule.globs.as_deref() {
+                Some(globs) if !globs.is_empty() => {
+                    // Windsurf lists globs comma-separated
+                    Ok(format!("trigger: glob\nglobs: {}\n", globs.join(", ")))
+                }
+                _ => anyhow::bail!("glob trigger requires globs to be specified"),
+            },
+            WindsurfTrigger::ModelDecision => {
+                let description = rule.description.as_deref().unwrap_or_default();
+                Ok(format!(
+                    "trigger: model_decision\ndescription: {description}\n"
+                ))
+            }
+            WindsurfTrigger::Manual => Ok("trigger: manual\n".to_string()),
+        }
+    }
+
+    /// Report which split rule each doc matches (None when split rules are not in effect)
+    pub async fn rule_coverage(&self) -> Result<Option<RuleCoverage>> {
+        if self.config.get_effective_output_mode("windsurf") != OutputMode::Split {
+            return Ok(None);
+        }
+        let Some(rules) = self.get_split_rules() else {
+            return Ok(None);
+        };
+
+        let merger = MarkdownMerger::new_for_agent(self.config.clone(), "windsurf");
+        let docs = merger.get_split_documents().await?;
+        let doc_paths: Vec<String> = docs.iter().map(|doc| doc.path.clone()).collect();
+        let patterns: Vec<&[String]> = rules
+            .iter()
+            .map(|rule| rule.file_patterns.as_slice())
+            .collect();
+
+        let mut coverage =
+            RuleCoverage::build("windsurf", &doc_paths, &patterns, |file_name, patterns| {
+                matches_any_pattern(file_name, patterns)
+            })?;
+        let frontmatter_docs: Vec<String> = docs
+            .iter()
+            .filter(|doc| doc.frontmatter.has_rule_metadata())
+            .map(|doc| doc.path.clone())
+            .collect();
+        coverage.mark_frontmatter_docs(&frontmatter_docs);
+        Ok(Some(coverage))
+    }
+
+    /// Warnings for generated files Windsurf would truncate
+    fn char_limit_warnings(&self, files: &[GeneratedFile]) -> Vec<String> {
+        let limit = match self.config.get_effective_output_mode("windsurf") {
+            OutputMode::Merged => LEGACY_RULES_CHAR_LIMIT,
+            OutputMode::Split => RULE_FILE_CHAR_LIMIT,
+        };
+        files
+            .iter()
+            .filter_map(|file| {
+                let chars = file.content.chars().count();
+                (chars > limit).then(|| {
+                    format!(
+                        "windsurf: {} has {chars} characters; Windsurf ignores content beyond {limit} characters",
+                        file.path
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Get split rules from Windsurf configuration
+    fn get_split_rules(&self) -> Option<&Vec<WindsurfSplitRule>> {
+        self.config
+            .agents
+            .windsurf
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+            .map(|split_config| &split_config.rules)
+    }
+
+    /// Get output path for merged mode
+    fn get_merged_output_path(&self) -> String {
+        if let Some(base_dir) = &self.base_dir {
+            format!("{base_dir}/.windsurfrules")
+        } else {
+            ".windsurfrules".to_string()
+        }
+    }
+
+    /// Get rules directory path for split mode
+    fn get_rules_dir(&self) -> String {
+        if let Some(base_dir) = &self.base_dir {
+            format!("{base_dir}/.windsurf/rules")
+        } else {
+            ".windsurf/rules".to_string()
+        }
+    }
+
+    /// Prepare .windsurf/rules/ directory (delete existing files)
+    async fn prepare_rules_directory(&self, rules_dir: &str) -> Result<()> {
+        self.remove_rule_files(rules_dir).await?;
+
+        // Create directory (if it doesn't exist)
+        fs::create_dir_all(rules_dir).await?;
+        Ok(())
+    }
+
+    /// Delete .md files in the rules directory (other files are left alone)
+    async fn remove_rule_files(&self, rules_dir: &str) -> Result<()> {
+        // Delete contents if directory exists
+        if fs::metadata(rules_dir).await.is_ok() {
+            let mut entries = fs::read_dir(rules_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+                    fs::remove_file(path).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AgentConfig, WindsurfAgentConfig, WindsurfConfig, WindsurfSplitConfig};
+    use tempfile::tempdir;
+    use tokio::fs;
+
+    fn create_test_config(base_dir: &str, output_mode: OutputMode) -> AIContextConfig {
+        AIContextConfig {
+            version: "1.0".to_string(),
+            output_mode: Some(output_mode),
+            include_filenames: Some(false),
+            base_docs_dir: base_dir.to_string(),
+            agents: AgentConfig::default(),
+            ..Default::default()
+        }
+    }
+
+    fn with_rules(mut config: AIContextConfig, rules: Vec<WindsurfSplitRule>) -> AIContextConfig {
+        config.agents.windsurf = WindsurfConfig::Advanced(WindsurfAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
+            split_config: Some(WindsurfSplitConfig { rules }),
+        });
+        config
+    }
+
+    fn rule(patterns: &[&str], trigger: Option<WindsurfTrigger>) -> WindsurfSplitRule {
+        WindsurfSplitRule {
+            file_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            trigger,
+            globs: None,
+            description: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_merged() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(docs_dir.join("rules.md"), "# Rules\nUse tabs.")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Merged);
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = WindsurfAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, format!("{base_dir}/.windsurfrules"));
+        assert!(files[0].content.contains("Use tabs."));
+        assert!(!files[0].content.starts_with("---"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_split_default_always_on() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("api")).await.unwrap();
+        fs::write(docs_dir.join("api/errors.md"), "# Errors")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split);
+        let base_dir = temp_dir.path().to_string_lossy().to_string();
+        let agent = WindsurfAgent::new_with_base_dir(config, base_dir.clone());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].path,
+            format!("{base_dir}/.windsurf/rules/api_errors.md")
+        );
+        assert_eq!(files[0].content, "---\ntrigger: always_on\n---\n\n# Errors");
+    }
+
+    #[tokio::test]
+    async fn test_split_rule_triggers() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        for name in ["rust.md", "review.md", "release.md", "style.md"] {
+            fs::write(docs_dir.join(name), "# Doc").await.unwrap();
+        }
+
+        let mut glob_rule = rule(&["rust*"], None);
+        glob_rule.globs = Some(vec!["**/*.rs".to_string(), "Cargo.toml".to_string()]);
+        let mut decision_rule = rule(&["review*"], Some(WindsurfTrigger::ModelDecision));
+        decision_rule.description = Some("Code review checklist".to_string());
+        let config = with_rules(
+            create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split),
+            vec![
+                glob_rule,
+                decision_rule,
+                rule(&["release*"], Some(WindsurfTrigger::Manual)),
+            ],
+        );
+        let agent =
+            WindsurfAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        let files = agent.generate().await.unwrap();
+        let content = |name: &str| {
+            files
+                .iter()
+                .find(|file| file.path.ends_with(name))
+                .map(|file| file.content.clone())
+                .unwrap()
+        };
+        assert!(
+            content("rust.md").starts_with("---\ntrigger: glob\nglobs: **/*.rs, Cargo.toml\n---\n")
+        );
+        assert!(content("review.md").starts_with(
+            "---\ntrigger: model_decision\ndescription: Code review checklist\n---\n"
+        ));
+        assert!(content("release.md").starts_with("---\ntrigger: manual\n---\n"));
+        assert!(content("style.md").starts_with("---\ntrigger: always_on\n---\n"));
+    }
+
+    #[tokio::test]
+    async fn test_split_glob_trigger_requires_globs() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("rust.md"), "# Rust")
+            .await
+            .unwrap();
+
+        let config = with_rules(
+            create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Split),
+            vec![rule(&["rust*"], Some(WindsurfTrigger::Glob))],
+        );
+        let agent = WindsurfAgent::new(config);
+
+        let error = agent.generate().await.unwrap_err();
+        assert!(error.to_string().contains("requires globs"));
+    }
+
+    #[tokio::test]
+    async fn test_split_doc_frontmatter_overrides_rule() {
+        let temp_dir = tempdir().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).await.unwrap();
+        fs::write(
+            docs_dir.join("rust.md"),
+            "---\nglobs: \"src/**/*.rs\"\n---\n\n# Rust",
+        )
+        .await
+        .unwrap();
+
+        let config = with_rules(
+            create_test_config(&docs_dir.to_string_lossy(), OutputMode::Split),
+            vec![rule(&["rust*"], Some(WindsurfTrigger::Manual))],
+        );
+        let agent =
+            WindsurfAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+
+        let files = agent.generate().await.unwrap();
+        assert_eq!(
+            files[0].content,
+            "---\ntrigger: glob\nglobs: src/**/*.rs\n---\n\n# Rust"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_rules_directory_removes_only_md_files() {
+        let temp_dir = tempdir().unwrap();
+        let rules_dir = temp_dir.path().join(".windsurf/rules");
+        fs::create_dir_all(&rules_dir).await.unwrap();
+        fs::write(rules_dir.join("old.md"), "old").await.unwrap();
+        fs::write(rules_dir.join("notes.txt"), "keep")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Split);
+        let agent =
+            WindsurfAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        agent
+            .prepare_rules_directory(&rules_dir.to_string_lossy())
+            .await
+            .unwrap();
+
+        assert!(!rules_dir.join("old.md").exists());
+        assert!(rules_dir.join("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clean_output_merged_removes_split_rules() {
+        let temp_dir = tempdir().unwrap();
+        let rules_dir = temp_dir.path().join(".windsurf/rules");
+        fs::create_dir_all(&rules_dir).await.unwrap();
+        fs::write(rules_dir.join("old.md"), "old").await.unwrap();
+        fs::write(rules_dir.join("notes.txt"), "keep")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Merged);
+        let agent =
+            WindsurfAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        agent.clean_output().await.unwrap();
+
+        assert!(!rules_dir.join("old.md").exists());
+        assert!(rules_dir.join("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clean_output_split_removes_windsurfrules() {
+        let temp_dir = tempdir().unwrap();
+        let merged_path = temp_dir.path().join(".windsurfrules");
+        fs::write(&merged_path, "old").await.unwrap();
+
+        let config = create_test_config(&temp_dir.path().to_string_lossy(), OutputMode::Split);
+        let agent =
+            WindsurfAgent::new_with_base_dir(config, temp_dir.path().to_string_lossy().to_string());
+        agent.clean_output().await.unwrap();
+
+        assert!(!merged_path.exists());
+        assert!(temp_dir.path().join(".windsurf/rules").is_dir());
+    }
+
+    #[test]
+    fn test_char_limit_warnings() {
+        let merged = WindsurfAgent::new(create_test_config("docs", OutputMode::Merged));
+        let split = WindsurfAgent::new(create_test_config("docs", OutputMode::Split));
+        let files = vec![
+            GeneratedFile::new(".windsurfrules".to_string(), "a".repeat(6_001)),
+            GeneratedFile::new("small.md".to_string(), "a".repeat(6_000)),
+        ];
+
+        let warnings = merged.char_limit_warnings(&files);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(".windsurfrules has 6001 characters"));
+        assert!(split.char_limit_warnings(&files).is_empty());
+
+        let large = vec![GeneratedFile::new(
+            "big.md".to_string(),
+            "あ".repeat(12_001),
+        )];
+        assert_eq!(split.char_limit_warnings(&large).len(), 1);
+    }
+}
@@ -27,8 +27,12 @@ impl ConfigLoader {
             .await
             .map_err(|e| ConfigError::IoError { source: e })?;
 
-        let config: AIContextConfig =
+        let mut config: AIContextConfig =
             serde_yaml::from_str(&content).map_err(|e| ConfigError::YamlError { source: e })?;
+        config.config_dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf);
 
         Self::validate_config(&config)?;
         Ok(config)
@@ -188,6 +192,7 @@ agents:
         assert_eq!(config.base_docs_dir, "./docs");
         assert!(config.agents.cursor.is_enabled());
         assert!(!config.agents.cline.is_enabled());
+        assert_eq!(config.get_packs_root(), temp_dir.path());
     }
 
     #[tokio::test]
@@ -0,0 +1,64 @@
+/*!
+ * AI Context Management Tool - Code Fence Tracking
+ *
+ * Line-by-line detection of fenced code blocks so directives inside them are left untouched
+ */
+
+/// Tracks fenced code blocks (``` or ~~~) while scanning lines
+#[derive(Debug, Default)]
+pub struct CodeFenceTracker {
+    /// Fence character and length of the open block
+    open: Option<(char, usize)>,
+}
+
+impl CodeFenceTracker {
+    /// Create a tracker outside any code block
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next line; returns true if it is a fence delimiter or inside a code block
+    pub fn update(&mut self, line: &str) -> bool {
+        let trimmed = line.trim();
+        let fence = ['`', '~'].into_iter().find_map(|c| {
+            let len = trimmed.chars().take_while(|&x| x == c).count();
+            (len >= 3).then_some((c, len))
+        });
+
+        match (self.open, fence) {
+            (None, Some(fence)) => {
+                self.open = Some(fence);
+                true
+            }
+            (Some((open_char, open_len)), Some((c, len)))
+                if c == open_char && len >= open_len && trimmed[len..].trim().is_empty() =>
+            {
+                self.open = None;
+                true
+            }
+            (open, _) => open.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_fenced_blocks() {
+        let mut tracker = CodeFenceTracker::new();
+        let states: Vec<bool> = [
+            "text", "```rust", "code", "~~~", "```", "after", "~~~~", "````", "~~~~",
+        ]
+        .iter()
+        .map(|line| tracker.update(line))
+        .collect();
+
+        assert_eq!(
+            states,
+            vec![false, true, true, true, true, false, true, true, true]
+        );
+        assert!(!tracker.update("done"));
+    }
+}
@@ -0,0 +1,253 @@
+/*!
+ * AI Context Management Tool - Conditional Blocks
+ *
+ * Evaluates `<!-- aicm:if agent=... -->` blocks in source docs for each agent
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use anyhow::{bail, Result};
+
+/// Condition of an `aicm:if` marker
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    /// `agent=a,b`: true if the agent is listed
+    AgentIn(Vec<String>),
+    /// `agent!=a,b`: true if the agent is not listed
+    AgentNotIn(Vec<String>),
+}
+
+impl Condition {
+    fn parse(line_number: usize, args: &str) -> Result<Self> {
+        let (negated, list) = if let Some(list) = args.strip_prefix("agent!=") {
+            (true, list)
+        } else if let Some(list) = args.strip_prefix("agent=") {
+            (false, list)
+        } else {
+            bail!("line {line_number}: unknown aicm:if condition '{args}' (expected agent=... or agent!=...)");
+        };
+
+        let agents: Vec<String> = list
+            .split(',')
+            .map(|agent| agent.trim().to_string())
+            .filter(|agent| !agent.is_empty())
+            .collect();
+        if agents.is_empty() {
+            bail!("line {line_number}: aicm:if condition '{args}' lists no agents");
+        }
+
+        Ok(if negated {
+            Condition::AgentNotIn(agents)
+        } else {
+            Condition::AgentIn(agents)
+        })
+    }
+
+    /// Evaluate for an agent (no agent never matches `agent=`)
+    fn matches(&self, agent: Option<&str>) -> bool {
+        let listed = |agents: &[String]| agent.is_some_and(|a| agents.iter().any(|x| x == a));
+        match self {
+            Condition::AgentIn(agents) => listed(agents),
+            Condition::AgentNotIn(agents) => !listed(agents),
+        }
+    }
+}
+
+/// Marker found on a line of its own
+#[derive(Debug, Clone, PartialEq)]
+enum Marker {
+    If(Condition),
+    Else,
+    Endif,
+}
+
+/// Open `aicm:if` block
+struct Block {
+    /// Line of the `aicm:if` marker
+    line_number: usize,
+    /// Result of the condition
+    condition: bool,
+    /// Line of the `aicm:else` marker, once seen
+    else_line: Option<usize>,
+}
+
+impl Block {
+    fn is_active(&self) -> bool {
+        self.condition != self.else_line.is_some()
+    }
+}
+
+/// Evaluate conditional blocks for the specified agent and remove the markers
+/// Markers inside fenced code blocks are left untouched
+pub fn evaluate_conditionals(content: &str, agent: Option<&str>) -> Result<String> {
+    let mut output = String::with_capacity(content.len());
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut fence = CodeFenceTracker::new();
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        let marker = if fence.update(line) {
+            None
+        } else {
+            parse_marker(line_number, trimmed)?
+        };
+
+        match marker {
+            Some(Marker::If(condition)) => blocks.push(Block {
+                line_number,
+                condition: condition.matches(agent),
+                else_line: None,
+            }),
+            Some(Marker::Else) => match blocks.last_mut() {
+                Some(block) => {
+                    if let Some(else_line) = block.else_line {
+                        bail!(
+                            "line {line_number}: duplicate aicm:else (aicm:if on line {} already has aicm:else on line {else_line})",
+                            block.line_number
+                        );
+                    }
+                    block.else_line = Some(line_number);
+                }
+                None => bail!("line {line_number}: aicm:else without matching aicm:if"),
+            },
+            Some(Marker::Endif) => {
+                if blocks.pop().is_none() {
+                    bail!("line {line_number}: aicm:endif without matching aicm:if");
+                }
+            }
+            None => {
+                if blocks.iter().all(Block::is_active) {
+                    output.push_str(line);
+                }
+            }
+        }
+    }
+
+    if let Some(block) = blocks.last() {
+        bail!(
+            "line {}: aicm:if is not closed with aicm:endif",
+            block.line_number
+        );
+    }
+
+    Ok(output)
+}
+
+/// Parse an `<!-- aicm:... -->` marker line (None for regular lines)
+fn parse_marker(line_number: usize, trimmed: &str) -> Result<Option<Marker>> {
+    let Some(directive) = trimmed
+        .strip_prefix("<!--")
+        .and_then(|rest| rest.strip_suffix("-->"))
+        .map(str::trim)
+        .and_then(|rest| rest.strip_prefix("aicm:"))
+    else {
+        return Ok(None);
+    };
+
+    let (name, args) = directive
+        .split_once(char::is_whitespace)
+        .map(|(name, args)| (name, args.trim()))
+        .unwrap_or((directive, ""));
+
+    match name {
+        "if" => Ok(Some(Marker::If(Condition::parse(line_number, args)?))),
+        "else" | "endif" if !args.is_empty() => {
+            bail!("line {line_number}: aicm:{name} does not take arguments")
+        }
+        "else" => Ok(Some(Marker::Else)),
+        "endif" => Ok(Some(Marker::Endif)),
+        // Expanded later by the include expander
+        "include" => Ok(None),
+        _ => bail!("line {line_number}: unknown marker 'aicm:{name}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "Intro\n<!-- aicm:if agent=claude,gemini -->\nUse /test\n<!-- aicm:else -->\nRun the test task\n<!-- aicm:endif -->\nOutro\n";
+
+    #[test]
+    fn test_if_else_per_agent() {
+        assert_eq!(
+            evaluate_conditionals(DOC, Some("claude")).unwrap(),
+            "Intro\nUse /test\nOutro\n"
+        );
+        assert_eq!(
+            evaluate_conditionals(DOC, Some("cursor")).unwrap(),
+            "Intro\nRun the test task\nOutro\n"
+        );
+        // Without an agent, agent= conditions never match
+        assert_eq!(
+            evaluate_conditionals(DOC, None).unwrap(),
+            "Intro\nRun the test task\nOutro\n"
+        );
+    }
+
+    #[test]
+    fn test_negation_and_nesting() {
+        let content = "<!-- aicm:if agent!=cursor -->\nA\n<!-- aicm:if agent=claude -->\nB\n<!-- aicm:endif -->\n<!-- aicm:endif -->\nC";
+        assert_eq!(
+            evaluate_conditionals(content, Some("claude")).unwrap(),
+            "A\nB\nC"
+        );
+        assert_eq!(
+            evaluate_conditionals(content, Some("github")).unwrap(),
+            "A\nC"
+        );
+        assert_eq!(evaluate_conditionals(content, Some("cursor")).unwrap(), "C");
+    }
+
+    #[test]
+    fn test_markers_in_code_fence_are_kept() {
+        let content = "```markdown\n<!-- aicm:if agent=claude -->\n```\nText\n";
+        assert_eq!(
+            evaluate_conditionals(content, Some("cursor")).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_markers_report_line() {
+        let err = evaluate_conditionals("A\n<!-- aicm:endif -->\n", None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("line 2: aicm:endif without matching aicm:if"));
+
+        let err = evaluate_conditionals("<!-- aicm:else -->", None).unwrap_err();
+        assert!(err.to_string().contains("line 1: aicm:else without"));
+
+        let err =
+            evaluate_conditionals("A\n\n<!-- aicm:if agent=claude -->\nB\n", None).unwrap_err();
+        assert!(err.to_string().contains("line 3: aicm:if is not closed"));
+
+        let content = "<!-- aicm:if agent=claude -->\n<!-- aicm:else -->\n<!-- aicm:else -->\n<!-- aicm:endif -->";
+        let err = evaluate_conditionals(content, None).unwrap_err();
+        assert!(err.to_string().contains("line 3: duplicate aicm:else"));
+    }
+
+    #[test]
+    fn test_unknown_markers_report_line() {
+        let err =
+            evaluate_conditionals("A\n<!-- aicm:unless agent=claude -->\n", None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("line 2: unknown marker 'aicm:unless'"));
+
+        let err = evaluate_conditionals("<!-- aicm:if lang=rust -->\n", None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("line 1: unknown aicm:if condition 'lang=rust'"));
+
+        let err = evaluate_conditionals("<!-- aicm:if agent= -->\n", None).unwrap_err();
+        assert!(err.to_string().contains("lists no agents"));
+    }
+
+    #[test]
+    fn test_regular_comments_are_kept() {
+        let content = "<!-- TODO: rewrite -->\nText";
+        assert_eq!(evaluate_conditionals(content, None).unwrap(), content);
+    }
+}
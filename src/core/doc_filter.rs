@@ -0,0 +1,221 @@
+/*!
+ * AI Context Management Tool - Doc Filter
+ *
+ * Selects which files under the docs directory are scanned
+ * (include/exclude globs, .aicmignore and default excluded directories)
+ */
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+
+/// Directories that are never scanned for docs
+pub const DEFAULT_EXCLUDED_DIRS: &[&str] = &[".git", "node_modules"];
+
+/// gitignore-syntax file read from the root of the docs directory
+pub const IGNORE_FILE_NAME: &str = ".aicmignore";
+
+/// Single .aicmignore rule
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: Pattern,
+    /// `!pattern`: re-include a previously ignored path
+    negated: bool,
+    /// `pattern/`: match directories only
+    dir_only: bool,
+    /// Pattern contains `/`: match against the full path instead of the name
+    anchored: bool,
+}
+
+/// Rules parsed from an .aicmignore file (gitignore syntax)
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Parse gitignore-syntax content
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let anchored = line.contains('/');
+            let line = line.strip_prefix('/').unwrap_or(line);
+
+            let pattern = Pattern::new(line).with_context(|| {
+                format!(
+                    "{IGNORE_FILE_NAME} line {}: invalid pattern '{line}'",
+                    index + 1
+                )
+            })?;
+            rules.push(IgnoreRule {
+                pattern,
+                negated,
+                dir_only,
+                anchored,
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Check whether a path relative to the docs directory (using '/') is ignored
+    /// A path is ignored if it or any of its parent directories is ignored
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        let components: Vec<&str> = path.split('/').collect();
+        (1..=components.len()).any(|len| {
+            let sub_path = components[..len].join("/");
+            let sub_is_dir = len < components.len() || is_dir;
+            self.matches(&sub_path, sub_is_dir)
+        })
+    }
+
+    /// Result of the last matching rule (false if no rule matches)
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let name = path.rsplit('/').next().unwrap_or(path);
+
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let target = if rule.anchored { path } else { name };
+            if rule.pattern.matches_with(target, options) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Decides which files under the docs directory are scanned
+#[derive(Debug, Clone, Default)]
+pub struct DocFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    ignore: IgnoreRules,
+}
+
+impl DocFilter {
+    /// Create a filter from include/exclude globs and .aicmignore rules
+    pub fn new(include: &[String], exclude: &[String], ignore: IgnoreRules) -> Result<Self> {
+        Ok(Self {
+            include: Self::compile(include, "include")?,
+            exclude: Self::compile(exclude, "exclude")?,
+            ignore,
+        })
+    }
+
+    fn compile(patterns: &[String], key: &str) -> Result<Vec<Pattern>> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).with_context(|| format!("Invalid {key} pattern: {pattern}"))
+            })
+            .collect()
+    }
+
+    /// Check whether a directory (relative path using '/') should be skipped while scanning
+    pub fn skips_dir(&self, path: &str) -> bool {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        DEFAULT_EXCLUDED_DIRS.contains(&name) || self.ignore.is_ignored(path, true)
+    }
+
+    /// Check whether a file (relative path using '/') should be loaded as a doc
+    pub fn accepts_file(&self, path: &str) -> bool {
+        if self.ignore.is_ignored(path, false) {
+            return false;
+        }
+        if !self.include.is_empty() && !self.include.iter().any(|p| p.matches(path)) {
+            return false;
+        }
+        !self.exclude.iter().any(|p| p.matches(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_ignore_rules_gitignore_syntax() {
+        let rules = IgnoreRules::parse(
+            "# comment\n\n*.draft.md\ndrafts/\n/README.md\nnotes/*.md\n!notes/keep.md\n",
+        )
+        .unwrap();
+
+        assert!(rules.is_ignored("idea.draft.md", false));
+        assert!(rules.is_ignored("guides/idea.draft.md", false));
+        assert!(rules.is_ignored("drafts", true));
+        assert!(rules.is_ignored("drafts/wip.md", false));
+        assert!(rules.is_ignored("guides/drafts/wip.md", false));
+        assert!(rules.is_ignored("README.md", false));
+        assert!(!rules.is_ignored("guides/README.md", false));
+        assert!(rules.is_ignored("notes/todo.md", false));
+        assert!(!rules.is_ignored("notes/keep.md", false));
+        assert!(!rules.is_ignored("notes/deep/todo.md", false));
+        assert!(!rules.is_ignored("guide.md", false));
+    }
+
+    #[test]
+    fn test_ignore_rules_dir_only_does_not_match_files() {
+        let rules = IgnoreRules::parse("drafts/\n").unwrap();
+        assert!(!rules.is_ignored("drafts", false));
+    }
+
+    #[test]
+    fn test_ignore_rules_invalid_pattern() {
+        let error = IgnoreRules::parse("ok.md\n[invalid\n").unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_doc_filter_include_exclude() {
+        let filter = DocFilter::new(
+            &patterns(&["guides/*", "overview.md"]),
+            &patterns(&["*/internal-*"]),
+            IgnoreRules::default(),
+        )
+        .unwrap();
+
+        assert!(filter.accepts_file("overview.md"));
+        assert!(filter.accepts_file("guides/testing.md"));
+        assert!(!filter.accepts_file("guides/internal-notes.md"));
+        assert!(!filter.accepts_file("drafts/idea.md"));
+    }
+
+    #[test]
+    fn test_doc_filter_default_excluded_dirs() {
+        let filter = DocFilter::default();
+        assert!(filter.skips_dir(".git"));
+        assert!(filter.skips_dir("web/node_modules"));
+        assert!(!filter.skips_dir("guides"));
+        assert!(filter.accepts_file("guide.md"));
+    }
+
+    #[test]
+    fn test_doc_filter_invalid_glob() {
+        let result = DocFilter::new(&patterns(&["[bad"]), &[], IgnoreRules::default());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,229 @@
+/*!
+ * AI Context Management Tool - Source Encoding
+ *
+ * Decodes source doc bytes (BOM stripping, transcoding, line-ending normalization)
+ */
+
+use crate::types::LineEnding;
+use anyhow::{bail, Context, Result};
+use encoding_rs::Encoding;
+
+/// Decoded source doc
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSource {
+    /// UTF-8 content (LF line endings unless normalization is disabled)
+    pub content: String,
+    /// Diagnostics worth reporting (e.g. converted or mixed line endings)
+    pub warnings: Vec<String>,
+}
+
+/// Decode source doc bytes into UTF-8, with LF line endings if `normalize_eol` is set
+/// UTF-8 and UTF-16 BOMs are honored and stripped; bytes that are not valid UTF-8 are
+/// transcoded from `fallback_encoding` if set, otherwise an error describes the problem
+pub fn decode_source(
+    bytes: &[u8],
+    fallback_encoding: Option<&str>,
+    normalize_eol: bool,
+) -> Result<DecodedSource> {
+    let content = if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        utf8(rest, fallback_encoding)?
+    } else if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        decode_utf16(rest, u16::from_le_bytes)?
+    } else if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        decode_utf16(rest, u16::from_be_bytes)?
+    } else {
+        utf8(bytes, fallback_encoding)?
+    };
+
+    let mut warnings = Vec::new();
+    let crlf = content.matches("\r\n").count();
+    let cr = content.matches('\r').count() - crlf;
+    let lf = content.matches('\n').count() - crlf;
+    let counts: Vec<String> = [(crlf, "CRLF"), (cr, "CR"), (lf, "LF")]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, kind)| format!("{count} {kind}"))
+        .collect();
+    if counts.len() > 1 {
+        let action = if normalize_eol {
+            "normalized to LF"
+        } else {
+            "left as written"
+        };
+        warnings.push(format!(
+            "mixed line endings ({}) {action}",
+            counts.join(", ")
+        ));
+    } else if normalize_eol && crlf > 0 {
+        warnings.push(format!("{crlf} CRLF line endings normalized to LF"));
+    } else if normalize_eol && cr > 0 {
+        warnings.push(format!("{cr} CR line endings normalized to LF"));
+    }
+
+    let content = if normalize_eol {
+        normalize_line_endings(&content)
+    } else {
+        content
+    };
+    Ok(DecodedSource { content, warnings })
+}
+
+/// Check whether bytes look like a binary file (NUL byte near the start, as git does)
+/// UTF-16 files with a BOM are not binary even though they contain NUL bytes
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    if bytes.starts_with(b"\xFF\xFE") || bytes.starts_with(b"\xFE\xFF") {
+        return false;
+    }
+    bytes.iter().take(8000).any(|&b| b == 0)
+}
+
+/// Convert CRLF and lone CR line endings to LF
+pub fn normalize_line_endings(content: &str) -> String {
+    if content.contains('\r') {
+        content.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        content.to_string()
+    }
+}
+
+/// Convert LF content to the configured output line ending
+pub fn apply_line_ending(content: &str, line_ending: &LineEnding) -> String {
+    match line_ending {
+        LineEnding::Lf => content.to_string(),
+        LineEnding::Crlf => normalize_line_endings(content).replace('\n', "\r\n"),
+    }
+}
+
+fn utf8(bytes: &[u8], fallback_encoding: Option<&str>) -> Result<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(content) => Ok(content.to_string()),
+        Err(e) => match fallback_encoding {
+            Some(encoding) => transcode(bytes, encoding),
+            None => bail!(
+                "not valid UTF-8 (invalid byte at offset {}); set source_encoding (e.g. shift_jis) to transcode it",
+                e.valid_up_to()
+            ),
+        },
+    }
+}
+
+/// Transcode bytes from the named encoding to UTF-8
+/// Accepts the WHATWG encoding labels (e.g. `shift_jis`, `euc-jp`, `windows-1252`, `utf-16le`)
+pub fn transcode(bytes: &[u8], encoding: &str) -> Result<String> {
+    let Some(decoder) = Encoding::for_label(encoding.as_bytes()) else {
+        bail!("unsupported source encoding: {encoding}");
+    };
+    match decoder.decode_without_bom_handling_and_without_replacement(bytes) {
+        Some(content) => Ok(content.into_owned()),
+        None => bail!("not valid {}", decoder.name()),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String> {
+    if !bytes.len().is_multiple_of(2) {
+        bail!("not valid UTF-16 (odd number of bytes)");
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).context("not valid UTF-16")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_strips_bom_and_normalizes_line_endings() {
+        let decoded = decode_source(b"\xEF\xBB\xBF# Title\r\nBody\r\n", None, true).unwrap();
+        assert_eq!(decoded.content, "# Title\nBody\n");
+        assert_eq!(
+            decoded.warnings,
+            vec!["2 CRLF line endings normalized to LF"]
+        );
+
+        let decoded = decode_source(b"a\rb\r", None, true).unwrap();
+        assert_eq!(decoded.content, "a\nb\n");
+        assert_eq!(decoded.warnings, vec!["2 CR line endings normalized to LF"]);
+
+        let decoded = decode_source(b"a\r\nb\nc", None, true).unwrap();
+        assert_eq!(decoded.content, "a\nb\nc");
+        assert_eq!(
+            decoded.warnings,
+            vec!["mixed line endings (1 CRLF, 1 LF) normalized to LF"]
+        );
+
+        let decoded = decode_source(b"a\nb\n", None, true).unwrap();
+        assert!(decoded.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_decode_without_line_ending_normalization() {
+        let decoded = decode_source(b"# Title\r\nBody\r\n", None, false).unwrap();
+        assert_eq!(decoded.content, "# Title\r\nBody\r\n");
+        assert!(decoded.warnings.is_empty());
+
+        let decoded = decode_source(b"a\r\nb\n", None, false).unwrap();
+        assert_eq!(decoded.content, "a\r\nb\n");
+        assert_eq!(
+            decoded.warnings,
+            vec!["mixed line endings (1 CRLF, 1 LF) left as written"]
+        );
+    }
+
+    #[test]
+    fn test_decode_utf16_bom() {
+        let decoded = decode_source(b"\xFF\xFEh\x00i\x00", None, true).unwrap();
+        assert_eq!(decoded.content, "hi");
+        let decoded = decode_source(b"\xFE\xFF\x00h\x00i", None, true).unwrap();
+        assert_eq!(decoded.content, "hi");
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        let error = decode_source(b"ok \x82\xa0", None, true).unwrap_err();
+        assert!(error.to_string().contains("offset 3"));
+        assert!(error.to_string().contains("source_encoding"));
+    }
+
+    #[test]
+    fn test_transcode_single_byte_encodings() {
+        assert_eq!(transcode(b"caf\xe9", "latin1").unwrap(), "café");
+        assert_eq!(
+            transcode(b"\x93quoted\x94 \x80", "windows-1252").unwrap(),
+            "\u{201C}quoted\u{201D} €"
+        );
+        assert_eq!(
+            decode_source(b"caf\xe9", Some("ISO-8859-1"), true)
+                .unwrap()
+                .content,
+            "café"
+        );
+    }
+
+    #[test]
+    fn test_transcode_shift_jis() {
+        let decoded = decode_source(b"\x82\xa0\x82\xa2\r\n", Some("shift_jis"), true).unwrap();
+        assert_eq!(decoded.content, "あい\n");
+        assert_eq!(transcode(b"\xa4\xa2", "EUC-JP").unwrap(), "あ");
+        assert!(transcode(b"\x82", "shift_jis").is_err());
+        assert!(transcode(b"abc", "no-such-encoding").is_err());
+    }
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(looks_binary(b"\x89PNG\r\n\x1a\n\x00\x00"));
+        assert!(!looks_binary(b"# Plain text"));
+        assert!(!looks_binary(b"\xFF\xFEh\x00i\x00"));
+    }
+
+    #[test]
+    fn test_apply_line_ending() {
+        assert_eq!(apply_line_ending("a\nb\n", &LineEnding::Lf), "a\nb\n");
+        assert_eq!(
+            apply_line_ending("a\nb\r\n", &LineEnding::Crlf),
+            "a\r\nb\r\n"
+        );
+    }
+}
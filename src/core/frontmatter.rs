@@ -0,0 +1,265 @@
+/*!
+ * AI Context Management Tool - Source Document Frontmatter
+ *
+ * Parses the YAML frontmatter block at the top of source docs
+ */
+
+use crate::types::InclusionMode;
+use anyhow::Result;
+use serde::{Deserialize, Deserializer};
+use serde_yaml::{Mapping, Value};
+
+/// Rule metadata declared in a source doc's frontmatter
+/// Values here take precedence over matching split_config rules
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct DocFrontmatter {
+    /// Agents that receive this doc (default: all agents)
+    #[serde(default)]
+    pub agents: Option<Vec<String>>,
+    /// Target file globs (Cursor globs, GitHub applyTo, Kiro fileMatchPattern, Windsurf globs)
+    #[serde(default, deserialize_with = "string_or_list")]
+    pub globs: Option<Vec<String>>,
+    /// Rule description (Cursor Agent Requested rule)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Always apply rule (Cursor alwaysApply)
+    #[serde(default)]
+    pub always_apply: Option<bool>,
+    /// Kiro inclusion mode (always, fileMatch, manual)
+    #[serde(default)]
+    pub kiro_inclusion: Option<InclusionMode>,
+    /// Output position among docs with the same config `order:` rank (lower first)
+    #[serde(default)]
+    pub order: Option<i64>,
+    /// Other keys (carried into generated frontmatter when source_frontmatter is merge)
+    #[serde(flatten)]
+    pub extra: Mapping,
+}
+
+impl DocFrontmatter {
+    /// Check whether the doc should be output for the specified agent
+    pub fn targets_agent(&self, agent: &str) -> bool {
+        match &self.agents {
+            Some(agents) => agents.iter().any(|a| a == agent),
+            None => true,
+        }
+    }
+
+    /// Check whether the doc declares any rule metadata
+    pub fn has_rule_metadata(&self) -> bool {
+        self.globs.is_some()
+            || self.description.is_some()
+            || self.always_apply.is_some()
+            || self.kiro_inclusion.is_some()
+    }
+
+    /// Render other keys as YAML lines to append to a generated frontmatter block
+    /// Keys in `generated_keys` are skipped and returned as conflicts (the generated value wins)
+    pub fn render_extra_keys(&self, generated_keys: &[&str]) -> Result<(String, Vec<String>)> {
+        let mut merged = Mapping::new();
+        let mut conflicts = Vec::new();
+
+        for (key, value) in &self.extra {
+            match key.as_str() {
+                Some(name) if generated_keys.contains(&name) => conflicts.push(name.to_string()),
+                _ => {
+                    merged.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        if merged.is_empty() {
+            return Ok((String::new(), conflicts));
+        }
+        Ok((serde_yaml::to_string(&merged)?, conflicts))
+    }
+}
+
+/// Accept both `globs: "**/*.rs"` and `globs: ["**/*.rs"]`
+fn string_or_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(
+        Option::<StringOrList>::deserialize(deserializer)?.map(|value| match value {
+            StringOrList::One(glob) => vec![glob],
+            StringOrList::Many(globs) => globs,
+        }),
+    )
+}
+
+/// Split the leading `---` frontmatter block from a document
+/// Returns the parsed frontmatter (None if the doc has no frontmatter) and the remaining body
+/// A leading block that is not a YAML mapping (e.g. a `---` horizontal rule followed by text
+/// and another rule) is kept as content; see `ignored_frontmatter_block`
+pub fn split_frontmatter(content: &str) -> Result<(Option<DocFrontmatter>, &str)> {
+    let Some((yaml, body)) = find_frontmatter_block(content) else {
+        return Ok((None, content));
+    };
+
+    let frontmatter = match parse_block(yaml) {
+        Ok(Some(mapping)) => serde_yaml::from_value(mapping)
+            .map_err(|e| anyhow::anyhow!("Invalid YAML frontmatter: {e}"))?,
+        Ok(None) => DocFrontmatter::default(),
+        Err(_) => return Ok((None, content)),
+    };
+
+    Ok((Some(frontmatter), body.trim_start_matches(['\r', '\n'])))
+}
+
+/// Explain why a leading `---` block was kept as content instead of parsed as frontmatter
+/// Returns None when the doc has frontmatter or does not start with a closed `---` block
+pub fn ignored_frontmatter_block(content: &str) -> Option<String> {
+    let (yaml, _) = find_frontmatter_block(content)?;
+    parse_block(yaml).err()
+}
+
+/// Parse a frontmatter block: a YAML mapping, None for an empty block, or why it is not frontmatter
+fn parse_block(yaml: &str) -> Result<Option<Value>, String> {
+    match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Null) => Ok(None),
+        Ok(mapping @ Value::Mapping(_)) => Ok(Some(mapping)),
+        Ok(_) => Err("not a YAML mapping".to_string()),
+        Err(e) => Err(format!("invalid YAML: {e}")),
+    }
+}
+
+/// Locate the frontmatter block, returning (yaml, body after the closing delimiter)
+fn find_frontmatter_block(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+
+    // No closing delimiter: treat as a regular document
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_frontmatter_without_frontmatter() {
+        let content = "# Title\n\nBody";
+        let (frontmatter, body) = split_frontmatter(content).unwrap();
+        assert!(frontmatter.is_none());
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn test_split_frontmatter_parses_and_strips() {
+        let content = "---\nagents: [claude, cursor]\nglobs: \"**/*.rs\"\ndescription: Rust rules\nalways_apply: false\nkiro_inclusion: fileMatch\n---\n\n# Rust\n";
+        let (frontmatter, body) = split_frontmatter(content).unwrap();
+        let frontmatter = frontmatter.unwrap();
+
+        assert_eq!(
+            frontmatter.agents,
+            Some(vec!["claude".to_string(), "cursor".to_string()])
+        );
+        assert_eq!(frontmatter.globs, Some(vec!["**/*.rs".to_string()]));
+        assert_eq!(frontmatter.description, Some("Rust rules".to_string()));
+        assert_eq!(frontmatter.always_apply, Some(false));
+        assert_eq!(frontmatter.kiro_inclusion, Some(InclusionMode::FileMatch));
+        assert_eq!(body, "# Rust\n");
+    }
+
+    #[test]
+    fn test_split_frontmatter_glob_list_and_crlf() {
+        let content = "---\r\nglobs: [\"**/*.ts\", \"**/*.tsx\"]\r\n---\r\nBody";
+        let (frontmatter, body) = split_frontmatter(content).unwrap();
+        assert_eq!(
+            frontmatter.unwrap().globs,
+            Some(vec!["**/*.ts".to_string(), "**/*.tsx".to_string()])
+        );
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_split_frontmatter_empty_block() {
+        let (frontmatter, body) = split_frontmatter("---\n---\nBody").unwrap();
+        assert_eq!(frontmatter, Some(DocFrontmatter::default()));
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_split_frontmatter_unclosed_block_is_content() {
+        let content = "---\nJust a horizontal rule at the top";
+        let (frontmatter, body) = split_frontmatter(content).unwrap();
+        assert!(frontmatter.is_none());
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn test_split_frontmatter_invalid_yaml_is_content() {
+        let content = "---\nagents: [claude\n---\nBody";
+        let (frontmatter, body) = split_frontmatter(content).unwrap();
+        assert!(frontmatter.is_none());
+        assert_eq!(body, content);
+        assert!(ignored_frontmatter_block(content)
+            .unwrap()
+            .starts_with("invalid YAML"));
+    }
+
+    #[test]
+    fn test_split_frontmatter_horizontal_rules_are_content() {
+        let content = "---\nIntro between two horizontal rules.\n---\n\n# Title\n";
+        let (frontmatter, body) = split_frontmatter(content).unwrap();
+        assert!(frontmatter.is_none());
+        assert_eq!(body, content);
+        assert_eq!(
+            ignored_frontmatter_block(content).as_deref(),
+            Some("not a YAML mapping")
+        );
+
+        // A mapping with invalid values is still frontmatter, and still an error
+        assert!(split_frontmatter("---\norder: first\n---\nBody").is_err());
+        assert!(ignored_frontmatter_block("---\nagents: [claude]\n---\nBody").is_none());
+    }
+
+    #[test]
+    fn test_targets_agent() {
+        let all = DocFrontmatter::default();
+        assert!(all.targets_agent("cursor"));
+
+        let claude_only = DocFrontmatter {
+            agents: Some(vec!["claude".to_string()]),
+            ..Default::default()
+        };
+        assert!(claude_only.targets_agent("claude"));
+        assert!(!claude_only.targets_agent("cursor"));
+        assert!(!claude_only.has_rule_metadata());
+    }
+
+    #[test]
+    fn test_render_extra_keys() {
+        let content = "---\nagents: [cursor]\nalwaysApply: true\ntitle: Rust rules\ntags: [lang, rust]\n---\nBody";
+        let (frontmatter, _) = split_frontmatter(content).unwrap();
+        let frontmatter = frontmatter.unwrap();
+        assert!(!frontmatter.has_rule_metadata());
+
+        let (keys, conflicts) = frontmatter
+            .render_extra_keys(&["description", "globs", "alwaysApply"])
+            .unwrap();
+        assert_eq!(keys, "title: Rust rules\ntags:\n- lang\n- rust\n");
+        assert_eq!(conflicts, vec!["alwaysApply".to_string()]);
+
+        let (keys, conflicts) = DocFrontmatter::default().render_extra_keys(&[]).unwrap();
+        assert!(keys.is_empty());
+        assert!(conflicts.is_empty());
+    }
+}
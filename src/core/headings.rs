@@ -0,0 +1,147 @@
+/*!
+ * AI Context Management Tool - Heading Normalization
+ *
+ * Nests doc headings under the section header added in merged output
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use crate::types::HeadingMode;
+
+/// Format a doc as a merged-output section with a `#` header
+/// The header is the doc path, or the doc's own H1 in doc_title mode
+pub fn format_doc_section(path: &str, content: &str, mode: &HeadingMode) -> String {
+    let content = content.trim();
+    let (title, body) = match mode {
+        HeadingMode::Keep => return format!("# {path}\n\n{content}"),
+        HeadingMode::Demote => (path.to_string(), content.to_string()),
+        HeadingMode::DocTitle => match take_title(content) {
+            Some((title, body)) => (title, body),
+            None => (path.to_string(), content.to_string()),
+        },
+    };
+
+    let body = nest_headings(&body);
+    let body = body.trim();
+    if body.is_empty() {
+        format!("# {title}")
+    } else {
+        format!("# {title}\n\n{body}")
+    }
+}
+
+/// Demote all ATX headings by one level if the doc uses H1, so they nest under a section header
+/// Headings inside fenced code blocks are left untouched; H6 stays H6
+pub fn nest_headings(content: &str) -> String {
+    let has_h1 = scan_headings(content).any(|(_, level)| level == Some(1));
+    if !has_h1 {
+        return content.to_string();
+    }
+
+    scan_headings(content)
+        .map(|(line, level)| match level {
+            Some(level) if level < 6 => {
+                let indent = line.len() - line.trim_start().len();
+                format!("{}#{}", &line[..indent], &line[indent..])
+            }
+            _ => line.to_string(),
+        })
+        .collect()
+}
+
+/// Remove the first H1 from the doc and return its text with the remaining body
+fn take_title(content: &str) -> Option<(String, String)> {
+    let mut body = String::with_capacity(content.len());
+    let mut title = None;
+
+    for (line, level) in scan_headings(content) {
+        if title.is_none() && level == Some(1) {
+            title = Some(line.trim().trim_start_matches('#').trim().to_string());
+        } else {
+            body.push_str(line);
+        }
+    }
+
+    title.map(|title| (title, body))
+}
+
+/// Iterate lines (with line endings) and their ATX heading level outside code blocks
+pub(crate) fn scan_headings(content: &str) -> impl Iterator<Item = (&str, Option<usize>)> {
+    let mut fence = CodeFenceTracker::new();
+    content.split_inclusive('\n').map(move |line| {
+        if fence.update(line) {
+            (line, None)
+        } else {
+            (line, heading_level(line))
+        }
+    })
+}
+
+/// Get ATX heading level (`# ` to `###### `) of a line
+pub(crate) fn heading_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    let is_heading = (1..=6).contains(&level)
+        && (rest.trim_end_matches(['\r', '\n']).is_empty() || rest.starts_with([' ', '\t']));
+    is_heading.then_some(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Testing\n\nIntro\n\n## Unit\n\n```sh\n# not a heading\n```\n###### Deep\n";
+
+    #[test]
+    fn test_nest_headings() {
+        assert_eq!(
+            nest_headings(DOC),
+            "## Testing\n\nIntro\n\n### Unit\n\n```sh\n# not a heading\n```\n###### Deep\n"
+        );
+
+        // Docs without H1 already nest under the section header
+        let content = "## Section\nText\n";
+        assert_eq!(nest_headings(content), content);
+    }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(heading_level("# Title"), Some(1));
+        assert_eq!(heading_level("   ### Title\n"), Some(3));
+        assert_eq!(heading_level("#\n"), Some(1));
+        assert_eq!(heading_level("#hashtag"), None);
+        assert_eq!(heading_level("####### Seven"), None);
+        assert_eq!(heading_level("    # Indented code"), None);
+    }
+
+    #[test]
+    fn test_format_doc_section_modes() {
+        assert_eq!(
+            format_doc_section("testing.md", DOC, &HeadingMode::Keep),
+            format!("# testing.md\n\n{}", DOC.trim())
+        );
+        assert!(format_doc_section("testing.md", DOC, &HeadingMode::Demote)
+            .starts_with("# testing.md\n\n## Testing\n\nIntro\n\n### Unit"));
+        assert!(
+            format_doc_section("testing.md", DOC, &HeadingMode::DocTitle)
+                .starts_with("# Testing\n\nIntro\n\n## Unit\n\n```sh\n# not a heading")
+        );
+    }
+
+    #[test]
+    fn test_format_doc_section_doc_title_fallback() {
+        // Without H1 the path is used as title
+        assert_eq!(
+            format_doc_section("notes.md", "## Item\nText", &HeadingMode::DocTitle),
+            "# notes.md\n\n## Item\nText"
+        );
+        // Title-only doc
+        assert_eq!(
+            format_doc_section("empty.md", "# Only Title\n", &HeadingMode::DocTitle),
+            "# Only Title"
+        );
+    }
+}
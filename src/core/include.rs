@@ -0,0 +1,458 @@
+/*!
+ * AI Context Management Tool - Include Directives
+ *
+ * Expands `<!-- aicm:include path-or-glob -->` directives in source docs
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use crate::core::conditional::evaluate_conditionals;
+use crate::core::encoding::{decode_source, looks_binary};
+use crate::core::frontmatter::{ignored_frontmatter_block, split_frontmatter};
+use crate::core::links::rebase_links;
+use crate::core::mdx::strip_mdx_esm;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maximum nesting depth of include directives
+pub const MAX_INCLUDE_DEPTH: usize = 10;
+
+/// Determine the root that included files must stay within
+/// The configuration file's directory when known; otherwise the current directory, or the
+/// docs directory itself if it lies outside the current directory
+pub fn project_root_for(config_dir: Option<&Path>, docs_dir: &Path) -> Result<PathBuf> {
+    if let Some(config_dir) = config_dir {
+        return config_dir
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", config_dir.display()));
+    }
+    let current_dir = std::env::current_dir()?.canonicalize()?;
+    let docs_dir = docs_dir.canonicalize()?;
+    if docs_dir.starts_with(&current_dir) {
+        Ok(current_dir)
+    } else {
+        Ok(docs_dir)
+    }
+}
+
+/// Expands include directives relative to the including doc
+/// Relative links in included files are re-based to the including doc's directory
+pub struct IncludeExpander {
+    root: PathBuf,
+    agent: Option<String>,
+    source_encoding: Option<String>,
+    normalize_line_endings: bool,
+    max_file_size: Option<u64>,
+}
+
+impl IncludeExpander {
+    /// Create an expander; included files must be inside `root`
+    /// Conditional blocks in included files are evaluated for `agent`
+    pub fn new(root: &Path, agent: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            root: root
+                .canonicalize()
+                .with_context(|| format!("Failed to resolve project root {}", root.display()))?,
+            agent: agent.map(str::to_string),
+            source_encoding: None,
+            normalize_line_endings: true,
+            max_file_size: None,
+        })
+    }
+
+    /// Decode included files that are not valid UTF-8 from `encoding`
+    pub fn with_source_encoding(mut self, encoding: Option<&str>) -> Self {
+        self.source_encoding = encoding.map(str::to_string);
+        self
+    }
+
+    /// Keep the line endings of included files as written when `normalize` is false
+    pub fn with_normalize_line_endings(mut self, normalize: bool) -> Self {
+        self.normalize_line_endings = normalize;
+        self
+    }
+
+    /// Reject included files larger than `max_file_size` bytes
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// Expand include directives in the content of the doc at `path`
+    pub fn expand(&self, path: &Path, content: &str) -> Result<String> {
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+        let mut chain = vec![path];
+        self.expand_chain(content, &mut chain)
+    }
+
+    fn expand_chain(&self, content: &str, chain: &mut Vec<PathBuf>) -> Result<String> {
+        let current = chain.last().cloned().unwrap_or_default();
+        let base_dir = current.parent().unwrap_or(Path::new("."));
+        let mut output = String::with_capacity(content.len());
+        let mut fence = CodeFenceTracker::new();
+
+        for line in content.split_inclusive('\n') {
+            let target = if fence.update(line) {
+                None
+            } else {
+                parse_include(line.trim())
+            };
+            let Some(target) = target else {
+                output.push_str(line);
+                continue;
+            };
+
+            let files = self.resolve(base_dir, target).with_context(|| {
+                format!(
+                    "Failed to resolve aicm:include {target} in {}",
+                    self.display(&current)
+                )
+            })?;
+
+            for file in files {
+                if chain.contains(&file) {
+                    chain.push(file);
+                    bail!("Include cycle detected: {}", self.display_chain(chain));
+                }
+                if chain.len() > MAX_INCLUDE_DEPTH {
+                    chain.push(file);
+                    bail!(
+                        "Include depth limit ({MAX_INCLUDE_DEPTH}) exceeded: {}",
+                        self.display_chain(chain)
+                    );
+                }
+
+                let raw = self.read(&file)?;
+                let raw =
+                    evaluate_conditionals(&raw, self.agent.as_deref()).with_context(|| {
+                        format!(
+                            "Failed to evaluate conditional blocks in {}",
+                            self.display(&file)
+                        )
+                    })?;
+                if let Some(reason) = ignored_frontmatter_block(&raw) {
+                    eprintln!(
+                        "Warning: {}: leading '---' block is not frontmatter ({reason}); keeping it as content",
+                        self.display(&file)
+                    );
+                }
+                let (_, body) = split_frontmatter(&raw).with_context(|| {
+                    format!("Failed to parse frontmatter of {}", self.display(&file))
+                })?;
+
+                // Links in the fragment are relative to the fragment, not the including doc
+                let fragment_dir = file.parent().unwrap_or(Path::new("."));
+                let doc_dir = chain[0].parent().unwrap_or(Path::new("."));
+                let body = rebase_links(body, fragment_dir, doc_dir);
+
+                chain.push(file);
+                let expanded = self.expand_chain(&body, chain)?;
+                chain.pop();
+
+                let expanded = expanded.trim_end();
+                if !expanded.is_empty() {
+                    output.push_str(expanded);
+                    output.push('\n');
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Read an included file with the same size, binary and encoding checks as docs
+    fn read(&self, file: &Path) -> Result<String> {
+        let display = self.display(file);
+        let bytes = fs::read(file).with_context(|| format!("Failed to read {display}"))?;
+        if let Some(max_file_size) = self.max_file_size {
+            if bytes.len() as u64 > max_file_size {
+                bail!(
+                    "Included file {display} ({} bytes) exceeds max_file_size ({max_file_size})",
+                    bytes.len()
+                );
+            }
+        }
+        if looks_binary(&bytes) {
+            bail!("Included file {display} is a binary file");
+        }
+
+        let decoded = decode_source(
+            &bytes,
+            self.source_encoding.as_deref(),
+            self.normalize_line_endings,
+        )
+        .with_context(|| format!("Failed to decode {display}"))?;
+        for warning in &decoded.warnings {
+            eprintln!("Warning: {display}: {warning}");
+        }
+        if file.extension().is_some_and(|ext| ext == "mdx") {
+            return Ok(strip_mdx_esm(&decoded.content));
+        }
+        Ok(decoded.content)
+    }
+
+    /// Resolve a path or glob relative to the including doc's directory
+    fn resolve(&self, base_dir: &Path, target: &str) -> Result<Vec<PathBuf>> {
+        if target.is_empty() {
+            bail!("aicm:include requires a path");
+        }
+
+        let candidates: Vec<PathBuf> = if target.contains(['*', '?', '[']) {
+            let base = glob::Pattern::escape(&base_dir.to_string_lossy());
+            let pattern = format!("{base}/{target}");
+            let mut matches: Vec<PathBuf> = glob::glob(&pattern)?
+                .filter_map(|entry| entry.ok())
+                .filter(|path| path.is_file())
+                .collect();
+            if matches.is_empty() {
+                bail!("pattern matches no files");
+            }
+            matches.sort();
+            matches
+        } else {
+            vec![base_dir.join(target)]
+        };
+
+        let mut files = Vec::new();
+        for candidate in candidates {
+            let file = candidate
+                .canonicalize()
+                .with_context(|| format!("file not found: {}", candidate.display()))?;
+            if !file.starts_with(&self.root) {
+                bail!(
+                    "{} is outside the project root {}",
+                    file.display(),
+                    self.root.display()
+                );
+            }
+            files.push(file);
+        }
+        Ok(files)
+    }
+
+    /// Display a path relative to the project root
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    fn display_chain(&self, chain: &[PathBuf]) -> String {
+        chain
+            .iter()
+            .map(|path| self.display(path))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+/// Parse an `<!-- aicm:include target -->` line, returning the target
+fn parse_include(trimmed: &str) -> Option<&str> {
+    let directive = trimmed
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim()
+        .strip_prefix("aicm:include")?;
+    if !directive.is_empty() && !directive.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(directive.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, path: &str, content: &str) -> PathBuf {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_expand_relative_path_and_glob() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        write(
+            root,
+            "shared/security.md",
+            "---\nagents: [claude]\n---\nNo secrets\n",
+        );
+        write(root, "docs/snippets/a.md", "Snippet A");
+        write(root, "docs/snippets/b.md", "Snippet B\n\n");
+        let doc = write(
+            root,
+            "docs/guide.md",
+            "# Guide\n<!-- aicm:include ../shared/security.md -->\n<!-- aicm:include snippets/*.md -->\nEnd\n",
+        );
+
+        let expander = IncludeExpander::new(root, Some("claude")).unwrap();
+        let content = fs::read_to_string(&doc).unwrap();
+        assert_eq!(
+            expander.expand(&doc, &content).unwrap(),
+            "# Guide\nNo secrets\nSnippet A\nSnippet B\nEnd\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_include_evaluates_conditionals() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        write(
+            root,
+            "docs/parts/inner.md",
+            "<!-- aicm:if agent=cursor -->\nCursor only\n<!-- aicm:else -->\nOthers\n<!-- aicm:endif -->\n",
+        );
+        write(
+            root,
+            "docs/parts/outer.md",
+            "<!-- aicm:include inner.md -->",
+        );
+        let doc = write(
+            root,
+            "docs/main.md",
+            "<!-- aicm:include parts/outer.md -->\n",
+        );
+
+        let expander = IncludeExpander::new(root, Some("cursor")).unwrap();
+        assert_eq!(
+            expander
+                .expand(&doc, "<!-- aicm:include parts/outer.md -->\n")
+                .unwrap(),
+            "Cursor only\n"
+        );
+    }
+
+    #[test]
+    fn test_include_cycle_reports_chain() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "docs/b.md", "<!-- aicm:include a.md -->");
+        let doc = write(root, "docs/a.md", "<!-- aicm:include b.md -->");
+
+        let expander = IncludeExpander::new(root, None).unwrap();
+        let error = expander
+            .expand(&doc, "<!-- aicm:include b.md -->")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Include cycle detected: docs/a.md -> docs/b.md -> docs/a.md"
+        );
+    }
+
+    #[test]
+    fn test_include_depth_limit() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        for i in 0..=MAX_INCLUDE_DEPTH + 1 {
+            let next = i + 1;
+            write(
+                root,
+                &format!("docs/{i}.md"),
+                &format!("<!-- aicm:include {next}.md -->"),
+            );
+        }
+
+        let expander = IncludeExpander::new(root, None).unwrap();
+        let doc = root.join("docs/0.md");
+        let error = expander
+            .expand(&doc, "<!-- aicm:include 1.md -->")
+            .unwrap_err();
+        assert!(error.to_string().contains("depth limit"));
+    }
+
+    #[test]
+    fn test_include_outside_root_is_rejected() {
+        let temp_dir = tempdir().unwrap();
+        write(temp_dir.path(), "outside.md", "Outside");
+        let root = temp_dir.path().join("project");
+        let doc = write(&root, "docs/a.md", "");
+
+        let expander = IncludeExpander::new(&root, None).unwrap();
+        let error = expander
+            .expand(&doc, "<!-- aicm:include ../../outside.md -->")
+            .unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains("docs/a.md"));
+        assert!(message.contains("outside the project root"));
+    }
+
+    #[test]
+    fn test_included_links_are_relative_to_the_fragment() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "shared/diagrams/flow.png", "");
+        write(
+            root,
+            "shared/parts/deploy.md",
+            "See [flow](../diagrams/flow.png) and [home](https://example.com).\n",
+        );
+        write(
+            root,
+            "shared/overview.md",
+            "[guide]: guide.md\n<!-- aicm:include parts/deploy.md -->\n",
+        );
+        let doc = write(root, "docs/main.md", "");
+
+        let expander = IncludeExpander::new(root, None).unwrap();
+        assert_eq!(
+            expander
+                .expand(&doc, "[local](local.md)\n<!-- aicm:include ../shared/overview.md -->\n")
+                .unwrap(),
+            "[local](local.md)\n[guide]: ../shared/guide.md\nSee [flow](../shared/diagrams/flow.png) and [home](https://example.com).\n"
+        );
+    }
+
+    #[test]
+    fn test_included_files_are_size_and_binary_checked() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "docs/large.md", &"x".repeat(64));
+        write(root, "docs/image.md", "\0PNG");
+        write(
+            root,
+            "docs/widget.mdx",
+            "import Widget from './Widget'\n\n# Widget\n",
+        );
+        let doc = write(root, "docs/a.md", "");
+        let expander = IncludeExpander::new(root, None)
+            .unwrap()
+            .with_max_file_size(48);
+
+        let error = expander
+            .expand(&doc, "<!-- aicm:include large.md -->")
+            .unwrap_err();
+        assert!(error.to_string().contains("exceeds max_file_size (48)"));
+        let error = expander
+            .expand(&doc, "<!-- aicm:include image.md -->")
+            .unwrap_err();
+        assert!(error.to_string().contains("binary file"));
+        assert_eq!(
+            expander
+                .expand(&doc, "<!-- aicm:include widget.mdx -->")
+                .unwrap(),
+            "# Widget\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_file_and_directive_in_code_fence() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let doc = write(root, "docs/a.md", "");
+        let expander = IncludeExpander::new(root, None).unwrap();
+
+        let error = expander
+            .expand(&doc, "<!-- aicm:include missing.md -->")
+            .unwrap_err();
+        assert!(format!("{error:#}").contains("file not found"));
+
+        let fenced = "```\n<!-- aicm:include missing.md -->\n```\n";
+        assert_eq!(expander.expand(&doc, fenced).unwrap(), fenced);
+    }
+}
@@ -0,0 +1,333 @@
+/*!
+ * AI Context Management Tool - Link Rewriting
+ *
+ * Re-bases relative Markdown link and image targets from the source doc to the generated file
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use std::path::{Component, Path, PathBuf};
+
+/// Result of rewriting the links of one doc
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewrittenLinks {
+    pub content: String,
+    /// Relative targets that do not exist on disk (left unchanged)
+    pub unresolved: Vec<String>,
+}
+
+/// Rewrite relative targets of inline links, images and reference definitions
+/// so they resolve from `output_dir` instead of `source_dir` (both absolute)
+/// URLs, absolute paths, anchors and targets inside code are left untouched
+pub fn rewrite_links(content: &str, source_dir: &Path, output_dir: &Path) -> RewrittenLinks {
+    let source_dir = normalize_path(source_dir);
+    let output_dir = normalize_path(output_dir);
+    let mut unresolved = Vec::new();
+
+    let content = map_link_targets(content, |_, target| {
+        if !is_relative_target(target) {
+            return target.to_string();
+        }
+        rebase_target(target, &source_dir, &output_dir).unwrap_or_else(|| {
+            unresolved.push(target.to_string());
+            target.to_string()
+        })
+    });
+
+    RewrittenLinks {
+        content,
+        unresolved,
+    }
+}
+
+/// Re-base relative targets from `source_dir` to `target_dir` without checking that they exist
+/// Used for included fragments, whose links are relative to the fragment rather than the including doc
+pub fn rebase_links(content: &str, source_dir: &Path, target_dir: &Path) -> String {
+    let source_dir = normalize_path(source_dir);
+    let target_dir = normalize_path(target_dir);
+    if source_dir == target_dir {
+        return content.to_string();
+    }
+
+    map_link_targets(content, |_, target| {
+        if !is_relative_target(target) {
+            return target.to_string();
+        }
+        let (resolved, suffix) = resolve_target(target, &source_dir);
+        relative_target(&resolved, suffix, &target_dir).unwrap_or_else(|| target.to_string())
+    })
+}
+
+/// Collect the targets of inline links, images and reference definitions with their 1-based line
+/// Targets inside code blocks and code spans are skipped
+pub fn link_targets(content: &str) -> Vec<(usize, String)> {
+    let mut targets = Vec::new();
+    map_link_targets(content, |line, target| {
+        targets.push((line, target.to_string()));
+        target.to_string()
+    });
+    targets
+}
+
+/// Resolve `.` and `..` components without touching the file system
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Check whether a link target is a relative file path (not a URL, anchor or absolute path)
+pub fn is_relative_target(target: &str) -> bool {
+    if target.is_empty() || target.starts_with(['#', '/', '\\', '~', '{']) {
+        return false;
+    }
+    // URL schemes such as https:, mailto: or vscode:
+    let has_scheme = target
+        .find(':')
+        .filter(|&colon| {
+            colon > 1
+                && target[..colon]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'))
+        })
+        .is_some();
+    !has_scheme
+}
+
+/// Split a relative target into the file path it refers to (`%20` decoded) and its `#fragment`
+pub fn split_link_target(target: &str) -> (String, Option<&str>) {
+    let end = target.find(['#', '?']).unwrap_or(target.len());
+    let fragment = target.find('#').map(|index| &target[index + 1..]);
+    (target[..end].replace("%20", " "), fragment)
+}
+
+/// Apply `map` (given the 1-based line and target) to every link target outside code
+fn map_link_targets(content: &str, map: impl FnMut(usize, &str) -> String) -> String {
+    let mut rewriter = LinkRewriter { line: 0, map };
+    let mut fence = CodeFenceTracker::new();
+    let mut output = String::with_capacity(content.len());
+
+    for line in content.split_inclusive('\n') {
+        rewriter.line += 1;
+        if fence.update(line) {
+            output.push_str(line);
+        } else if let Some(line) = rewriter.reference_definition(line) {
+            output.push_str(&line);
+        } else {
+            output.push_str(&rewriter.inline_links(line));
+        }
+    }
+    output
+}
+
+/// Re-base a relative target; returns None if it does not exist on disk
+fn rebase_target(target: &str, source_dir: &Path, output_dir: &Path) -> Option<String> {
+    let (resolved, suffix) = resolve_target(target, source_dir);
+    if !resolved.exists() {
+        return None;
+    }
+    Some(relative_target(&resolved, suffix, output_dir).unwrap_or_else(|| target.to_string()))
+}
+
+/// Resolve a relative target against `source_dir`, returning the path and its `#`/`?` suffix
+fn resolve_target<'a>(target: &'a str, source_dir: &Path) -> (PathBuf, &'a str) {
+    let split = target.find(['#', '?']).unwrap_or(target.len());
+    let (path, suffix) = target.split_at(split);
+    (
+        normalize_path(&source_dir.join(path.replace("%20", " "))),
+        suffix,
+    )
+}
+
+/// Express a resolved path (plus suffix) relative to `base_dir`
+fn relative_target(resolved: &Path, suffix: &str, base_dir: &Path) -> Option<String> {
+    let relative = pathdiff::diff_paths(resolved, base_dir)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let relative = if relative.is_empty() { "." } else { &relative };
+    Some(format!("{}{suffix}", relative.replace(' ', "%20")))
+}
+
+struct LinkRewriter<F> {
+    line: usize,
+    map: F,
+}
+
+impl<F: FnMut(usize, &str) -> String> LinkRewriter<F> {
+    /// Rewrite `[label]: target "title"` lines
+    fn reference_definition(&mut self, line: &str) -> Option<String> {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if indent > 3 || !line[indent..].starts_with('[') {
+            return None;
+        }
+        let label_end = line[indent..].find("]:")? + indent + 2;
+        let rest = &line[label_end..];
+        let target_start = label_end + (rest.len() - rest.trim_start().len());
+        let (start, end) = target_span(&line[target_start..], false)?;
+        let (start, end) = (target_start + start, target_start + end);
+
+        Some(format!(
+            "{}{}{}",
+            &line[..start],
+            (self.map)(self.line, &line[start..end]),
+            &line[end..]
+        ))
+    }
+
+    /// Rewrite `[text](target)` and `![alt](target)` outside inline code spans
+    fn inline_links(&mut self, line: &str) -> String {
+        let mut output = String::with_capacity(line.len());
+        let mut rest = line;
+
+        while let Some(index) = rest.find(['`', ']']) {
+            if rest[index..].starts_with('`') {
+                // Copy the code span verbatim
+                let ticks = rest[index..].chars().take_while(|&c| c == '`').count();
+                let after = index + ticks;
+                let end = rest[after..]
+                    .find(&"`".repeat(ticks))
+                    .map_or(rest.len(), |end| after + end + ticks);
+                output.push_str(&rest[..end]);
+                rest = &rest[end..];
+                continue;
+            }
+
+            output.push_str(&rest[..=index]);
+            rest = &rest[index + 1..];
+            let Some(after_paren) = rest.strip_prefix('(') else {
+                continue;
+            };
+            output.push('(');
+            rest = after_paren;
+
+            let leading = rest.len() - rest.trim_start().len();
+            if let Some((start, end)) = target_span(&rest[leading..], true) {
+                let (start, end) = (leading + start, leading + end);
+                output.push_str(&rest[..start]);
+                output.push_str(&(self.map)(self.line, &rest[start..end]));
+                rest = &rest[end..];
+            }
+        }
+
+        output.push_str(rest);
+        output
+    }
+}
+
+/// Byte range of the link destination at the start of `text` (without angle brackets)
+fn target_span(text: &str, inline: bool) -> Option<(usize, usize)> {
+    if let Some(inner) = text.strip_prefix('<') {
+        let end = inner.find('>')?;
+        return Some((1, 1 + end));
+    }
+
+    let mut depth = 0usize;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' if inline => depth += 1,
+            ')' if inline && depth == 0 => return (index > 0).then_some((0, index)),
+            ')' if inline => depth -= 1,
+            c if c.is_whitespace() => return (index > 0).then_some((0, index)),
+            _ => {}
+        }
+    }
+    (!inline && !text.is_empty()).then_some((0, text.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn setup() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(root.join("ai-context/diagrams")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("ai-context/diagrams/arch.png"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::write(root.join("ai-context/guide.md"), "").unwrap();
+        let source_dir = root.join("ai-context");
+        (temp_dir, root, source_dir)
+    }
+
+    #[test]
+    fn test_rewrite_links_for_output_dir() {
+        let (_temp_dir, root, source_dir) = setup();
+        let content = "See [lib](../src/lib.rs \"Library\") and ![arch](./diagrams/arch.png).\n[guide]: guide.md#setup\n";
+
+        let rewritten = rewrite_links(content, &source_dir, &root.join(".cursor/rules"));
+        assert_eq!(
+            rewritten.content,
+            "See [lib](../../src/lib.rs \"Library\") and ![arch](../../ai-context/diagrams/arch.png).\n[guide]: ../../ai-context/guide.md#setup\n"
+        );
+        assert!(rewritten.unresolved.is_empty());
+
+        let rewritten = rewrite_links(content, &source_dir, &root);
+        assert!(rewritten
+            .content
+            .starts_with("See [lib](src/lib.rs \"Library\")"));
+        assert!(rewritten
+            .content
+            .contains("![arch](ai-context/diagrams/arch.png)"));
+    }
+
+    #[test]
+    fn test_rebase_links_without_existence_check() {
+        let root = Path::new("/project");
+        let content = "[a](a.md#top) [b](../missing.md) [c](https://example.com)\n";
+        assert_eq!(
+            rebase_links(content, &root.join("shared"), &root.join("docs/guides")),
+            "[a](../../shared/a.md#top) [b](../../missing.md) [c](https://example.com)\n"
+        );
+        assert_eq!(rebase_links(content, root, root), content);
+    }
+
+    #[test]
+    fn test_rewrite_links_leaves_urls_anchors_and_code() {
+        let (_temp_dir, root, source_dir) = setup();
+        let content = "[web](https://example.com) [top](#intro) [mail](mailto:a@b.c) `[x](../src/lib.rs)`\n```md\n[lib](../src/lib.rs)\n```\n";
+
+        let rewritten = rewrite_links(content, &source_dir, &root.join(".github"));
+        assert_eq!(rewritten.content, content);
+        assert!(rewritten.unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_link_targets() {
+        let content = "See [a](guide.md#setup) and ![b](<my diagram.png>)\n\n```\n[c](skipped.md)\n```\n[d]: ../src/lib.rs \"Lib\"\n";
+        assert_eq!(
+            link_targets(content),
+            vec![
+                (1, "guide.md#setup".to_string()),
+                (1, "my diagram.png".to_string()),
+                (6, "../src/lib.rs".to_string())
+            ]
+        );
+        assert_eq!(
+            split_link_target("my%20guide.md?raw=1#setup"),
+            ("my guide.md".to_string(), Some("setup"))
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_reports_unresolved_targets() {
+        let (_temp_dir, root, source_dir) = setup();
+        let content = "[missing](../docs/missing.md) [ok](guide.md)\n";
+
+        let rewritten = rewrite_links(content, &source_dir, &root.join(".github"));
+        assert_eq!(
+            rewritten.content,
+            "[missing](../docs/missing.md) [ok](../ai-context/guide.md)\n"
+        );
+        assert_eq!(rewritten.unresolved, vec!["../docs/missing.md"]);
+    }
+}
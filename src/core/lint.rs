@@ -0,0 +1,493 @@
+/*!
+ * AI Context Management Tool - Doc Linting
+ *
+ * Checks source docs for broken links, empty docs, colliding headings and frontmatter mistakes
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use crate::core::encoding::{decode_source, looks_binary};
+use crate::core::frontmatter::{ignored_frontmatter_block, split_frontmatter};
+use crate::core::headings::scan_headings;
+use crate::core::links::{is_relative_target, link_targets, normalize_path, split_link_target};
+use crate::core::mdx::strip_mdx_esm;
+use crate::core::toc::{github_slug, heading_anchors, heading_text};
+use crate::core::tokens::estimate_tokens;
+use crate::types::AIContextConfig;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Frontmatter keys recognized by aicm, used to spot misplaced frontmatter blocks
+const FRONTMATTER_KEYS: &[&str] = &[
+    "agents",
+    "globs",
+    "description",
+    "always_apply",
+    "kiro_inclusion",
+    "order",
+];
+
+/// Severity of a lint finding (errors make `aicm lint` fail)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+/// Problem found in a source doc
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFinding {
+    /// Doc path relative to its docs directory
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    pub severity: LintSeverity,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            LintSeverity::Error => "error",
+            LintSeverity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{}:{}: {severity} [{}] {}",
+            self.path, self.line, self.rule, self.message
+        )
+    }
+}
+
+/// Source doc decoded for linting
+struct LintDoc {
+    file_path: PathBuf,
+    path: String,
+    content: String,
+}
+
+/// Lints source docs found by the Markdown merger
+pub struct DocLinter {
+    config: AIContextConfig,
+    max_doc_tokens: usize,
+}
+
+impl DocLinter {
+    /// Create a linter reporting docs above `max_doc_tokens` estimated tokens
+    pub fn new(config: AIContextConfig, max_doc_tokens: usize) -> Self {
+        Self {
+            config,
+            max_doc_tokens,
+        }
+    }
+
+    /// Lint source doc files given as (file path, doc path); findings are sorted by doc and line
+    pub fn lint(&self, files: &[(PathBuf, String)]) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        let mut docs = Vec::new();
+
+        for (file_path, path) in files {
+            match self.read_doc(file_path) {
+                Ok(content) => docs.push(LintDoc {
+                    file_path: normalize_path(file_path),
+                    path: path.clone(),
+                    content,
+                }),
+                Err(message) => {
+                    findings.push(finding(path, 1, LintSeverity::Error, "unreadable", message))
+                }
+            }
+        }
+
+        let contents: HashMap<&Path, &str> = docs
+            .iter()
+            .map(|doc| (doc.file_path.as_path(), doc.content.as_str()))
+            .collect();
+        for doc in &docs {
+            self.lint_doc(doc, &contents, &mut findings);
+        }
+        lint_duplicate_headings(&docs, &mut findings);
+
+        findings.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        findings
+    }
+
+    fn lint_doc(
+        &self,
+        doc: &LintDoc,
+        contents: &HashMap<&Path, &str>,
+        findings: &mut Vec<LintFinding>,
+    ) {
+        let path = doc.path.as_str();
+        let (body, offset) = doc_body(&doc.content);
+
+        for (line, message) in frontmatter_problems(&doc.content) {
+            findings.push(finding(
+                path,
+                line,
+                LintSeverity::Error,
+                "frontmatter",
+                message,
+            ));
+        }
+
+        if body.trim().is_empty() {
+            findings.push(finding(
+                path,
+                1,
+                LintSeverity::Warning,
+                "empty-doc",
+                "doc is empty or whitespace-only and would generate an empty file".to_string(),
+            ));
+        }
+
+        let tokens = estimate_tokens(body);
+        if tokens > self.max_doc_tokens {
+            findings.push(finding(
+                path,
+                1,
+                LintSeverity::Warning,
+                "oversized-doc",
+                format!(
+                    "~{tokens} tokens exceeds the limit of {}",
+                    self.max_doc_tokens
+                ),
+            ));
+        }
+
+        let source_dir = doc.file_path.parent().unwrap_or(Path::new("."));
+        for (line, target) in link_targets(body) {
+            let line = line + offset;
+            if let Some(anchor) = target.strip_prefix('#') {
+                if !anchor.is_empty() && !has_anchor(body, anchor) {
+                    findings.push(finding(
+                        path,
+                        line,
+                        LintSeverity::Error,
+                        "broken-anchor",
+                        format!("anchor '#{anchor}' does not match any heading of this doc"),
+                    ));
+                }
+                continue;
+            }
+            if !is_relative_target(&target) {
+                continue;
+            }
+
+            let (file, anchor) = split_link_target(&target);
+            let resolved = normalize_path(&source_dir.join(&file));
+            if !resolved.exists() {
+                findings.push(finding(
+                    path,
+                    line,
+                    LintSeverity::Error,
+                    "broken-link",
+                    format!("link target '{target}' does not exist"),
+                ));
+                continue;
+            }
+
+            let Some(anchor) = anchor.filter(|anchor| !anchor.is_empty()) else {
+                continue;
+            };
+            let linked = match contents.get(resolved.as_path()) {
+                Some(content) => Some(content.to_string()),
+                None if resolved.is_file()
+                    && self.config.match_source_extension(&file).is_some() =>
+                {
+                    self.read_doc(&resolved).ok()
+                }
+                None => None,
+            };
+            if let Some(linked) = linked {
+                if !has_anchor(doc_body(&linked).0, anchor) {
+                    findings.push(finding(
+                        path,
+                        line,
+                        LintSeverity::Error,
+                        "broken-anchor",
+                        format!("anchor '#{anchor}' does not match any heading of {file}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Read and decode a doc the way the merger does (MDX statements stripped)
+    fn read_doc(&self, file_path: &Path) -> std::result::Result<String, String> {
+        let bytes = fs::read(file_path).map_err(|e| format!("failed to read: {e}"))?;
+        if looks_binary(&bytes) {
+            return Err("binary file".to_string());
+        }
+        let decoded = decode_source(
+            &bytes,
+            self.config.source_encoding.as_deref(),
+            self.config.get_normalize_line_endings(),
+        )
+        .map_err(|e| format!("{e:#}"))?;
+        let name = file_path.to_string_lossy();
+        if self.config.match_source_extension(&name) == Some("mdx") {
+            return Ok(strip_mdx_esm(&decoded.content));
+        }
+        Ok(decoded.content)
+    }
+}
+
+/// Report headings whose anchors collide with a heading of an earlier doc in merged output
+fn lint_duplicate_headings(docs: &[LintDoc], findings: &mut Vec<LintFinding>) {
+    let mut first_seen: HashMap<String, (&str, usize)> = HashMap::new();
+
+    for doc in docs {
+        let (body, offset) = doc_body(&doc.content);
+        let mut seen_in_doc = Vec::new();
+        for (index, (line, level)) in scan_headings(body).enumerate() {
+            if level.is_none() {
+                continue;
+            }
+            let text = heading_text(line);
+            let slug = github_slug(&text);
+            if slug.is_empty() || seen_in_doc.contains(&slug) {
+                continue;
+            }
+            seen_in_doc.push(slug.clone());
+
+            match first_seen.get(&slug) {
+                Some((first_path, first_line)) => findings.push(finding(
+                    &doc.path,
+                    index + 1 + offset,
+                    LintSeverity::Warning,
+                    "duplicate-heading",
+                    format!(
+                        "heading '{text}' also appears in {first_path}:{first_line}; anchors collide in merged output"
+                    ),
+                )),
+                None => {
+                    first_seen.insert(slug, (&doc.path, index + 1 + offset));
+                }
+            }
+        }
+    }
+}
+
+/// Body of a doc (frontmatter removed) and the number of lines before it
+fn doc_body(content: &str) -> (&str, usize) {
+    let body = match split_frontmatter(content) {
+        Ok((_, body)) => body,
+        Err(_) => content,
+    };
+    let offset = content[..content.len() - body.len()].matches('\n').count();
+    (body, offset)
+}
+
+/// Check whether the doc has a heading with the given anchor
+fn has_anchor(content: &str, anchor: &str) -> bool {
+    let anchor = anchor.replace("%20", "-").to_lowercase();
+    heading_anchors(content).contains(&anchor)
+}
+
+/// Find frontmatter mistakes: invalid YAML, unclosed blocks and blocks not on the first line
+fn frontmatter_problems(content: &str) -> Vec<(usize, String)> {
+    let mut problems = Vec::new();
+    if let Err(e) = split_frontmatter(content) {
+        problems.push((1, e.to_string()));
+    }
+    // A block that looks like frontmatter but is not a YAML mapping is silently kept as content
+    if let Some(reason) = ignored_frontmatter_block(content) {
+        if content.lines().nth(1).is_some_and(is_frontmatter_key) {
+            problems.push((
+                1,
+                format!("frontmatter is not a YAML mapping ({reason}); it is treated as content"),
+            ));
+        }
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut fence = CodeFenceTracker::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if fence.update(line) || line.trim_end() != "---" {
+            index += 1;
+            continue;
+        }
+
+        // `---`, then frontmatter keys (and their nested values), then `---`
+        let keys = lines[index + 1..]
+            .iter()
+            .take_while(|line| {
+                is_frontmatter_key(line) || line.starts_with(' ') || line.starts_with("- ")
+            })
+            .count();
+        let closed = lines
+            .get(index + 1 + keys)
+            .is_some_and(|line| line.trim_end() == "---");
+        if keys == 0 || !is_frontmatter_key(lines[index + 1]) {
+            index += 1;
+            continue;
+        }
+
+        if !closed {
+            if index == 0 {
+                problems.push((
+                    1,
+                    "frontmatter block is never closed with '---'; it is treated as content"
+                        .to_string(),
+                ));
+            }
+        } else if lines[..index].iter().all(|line| line.trim().is_empty()) {
+            if index > 0 {
+                problems.push((
+                    index + 1,
+                    "frontmatter must start on the first line; it is treated as content"
+                        .to_string(),
+                ));
+            }
+        } else {
+            problems.push((
+                index + 1,
+                "frontmatter block after content is treated as text; move it to the top of the doc"
+                    .to_string(),
+            ));
+        }
+        index += keys + 1 + usize::from(closed);
+    }
+
+    problems
+}
+
+fn is_frontmatter_key(line: &str) -> bool {
+    FRONTMATTER_KEYS.iter().any(|key| {
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+    })
+}
+
+fn finding(
+    path: &str,
+    line: usize,
+    severity: LintSeverity,
+    rule: &'static str,
+    message: String,
+) -> LintFinding {
+    LintFinding {
+        path: path.to_string(),
+        line,
+        severity,
+        rule,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn lint(docs: &[(&str, &str)], max_doc_tokens: usize) -> Vec<(String, usize, &'static str)> {
+        let temp_dir = tempdir().unwrap();
+        let mut files = Vec::new();
+        for (path, content) in docs {
+            let file_path = temp_dir.path().join(path);
+            fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            fs::write(&file_path, content).unwrap();
+            files.push((file_path, path.to_string()));
+        }
+
+        DocLinter::new(AIContextConfig::default(), max_doc_tokens)
+            .lint(&files)
+            .into_iter()
+            .map(|finding| (finding.path, finding.line, finding.rule))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_links_and_anchors() {
+        let findings = lint(
+            &[
+                (
+                    "guide.md",
+                    "---\ndescription: Guide\n---\n# Guide\n\n## Setup\n\n[ok](rules/style.md#naming) [top](#guide)\n[missing](gone.md) [bad](rules/style.md#nope) [self](#nowhere)\n[web](https://example.com) `[code](gone.md)`\n",
+                ),
+                ("rules/style.md", "# Style\n\n## Naming\n\nSee [guide](../guide.md#setup).\n"),
+            ],
+            1000,
+        );
+        assert_eq!(
+            findings,
+            vec![
+                ("guide.md".to_string(), 9, "broken-link"),
+                ("guide.md".to_string(), 9, "broken-anchor"),
+                ("guide.md".to_string(), 9, "broken-anchor"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_empty_oversized_and_duplicate_headings() {
+        let findings = lint(
+            &[
+                ("a.md", "# A\n\n## Setup\n\nSome words here.\n"),
+                ("b.md", "# B\n\n```md\n## Setup\n```\n\n## Setup\n"),
+                ("big.md", &"word ".repeat(50)),
+                ("empty.md", "---\norder: 1\n---\n \n"),
+            ],
+            20,
+        );
+        assert_eq!(
+            findings,
+            vec![
+                ("b.md".to_string(), 7, "duplicate-heading"),
+                ("big.md".to_string(), 1, "oversized-doc"),
+                ("empty.md".to_string(), 1, "empty-doc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_frontmatter_mistakes() {
+        let findings = lint(
+            &[
+                ("invalid.md", "---\norder: first\n---\n# Invalid\n"),
+                ("unparsed.md", "---\nagents: [claude\n---\n# Unparsed\n"),
+                ("hr.md", "---\nIntro text\n---\n# Intro\n"),
+                ("late.md", "\n---\nglobs: \"**/*.rs\"\n---\n# Late\n"),
+                (
+                    "trailing.md",
+                    "# Trailing\n\nBody\n\n---\nagents:\n  - claude\n---\n",
+                ),
+                ("unclosed.md", "---\ndescription: Unclosed\n# Unclosed\n"),
+                ("rule.md", "# Rule\n\nText\n---\n\nMore text\n"),
+            ],
+            1000,
+        );
+        assert_eq!(
+            findings,
+            vec![
+                ("invalid.md".to_string(), 1, "frontmatter"),
+                ("late.md".to_string(), 2, "frontmatter"),
+                ("trailing.md".to_string(), 5, "frontmatter"),
+                ("unclosed.md".to_string(), 1, "frontmatter"),
+                ("unparsed.md".to_string(), 1, "frontmatter"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_finding_display() {
+        let finding = finding(
+            "guide.md",
+            3,
+            LintSeverity::Error,
+            "broken-link",
+            "link target 'gone.md' does not exist".to_string(),
+        );
+        assert_eq!(
+            finding.to_string(),
+            "guide.md:3: error [broken-link] link target 'gone.md' does not exist"
+        );
+    }
+}
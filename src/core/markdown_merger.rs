@@ -4,15 +4,44 @@
  * Simplified Markdown file merging functionality
  */
 
-use crate::types::AIContextConfig;
-use anyhow::Result;
-use std::path::Path;
+use crate::core::conditional::evaluate_conditionals;
+use crate::core::doc_filter::{DocFilter, IgnoreRules, IGNORE_FILE_NAME};
+use crate::core::encoding::{decode_source, looks_binary};
+use crate::core::frontmatter::{ignored_frontmatter_block, split_frontmatter, DocFrontmatter};
+use crate::core::headings::format_doc_section;
+use crate::core::include::{project_root_for, IncludeExpander};
+use crate::core::links::rewrite_links;
+use crate::core::mdx::strip_mdx_esm;
+use crate::core::packs::PackManager;
+use crate::core::sections::{split_sections, SectionSelector};
+use crate::core::template::TemplateContext;
+use crate::core::toc::insert_toc;
+use crate::core::tokens::estimate_tokens;
+use crate::types::{AIContextConfig, SymlinkPolicy};
+use anyhow::{Context, Result};
+use glob::Pattern;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
+/// Source document loaded from the docs directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceDoc {
+    /// Path relative to the docs directory (always uses '/')
+    pub path: String,
+    /// Document body (frontmatter stripped)
+    pub content: String,
+    /// Metadata declared in the document's frontmatter
+    pub frontmatter: DocFrontmatter,
+}
+
 /// Class for automatic Markdown file detection and merging
 pub struct MarkdownMerger {
     config: AIContextConfig,
     base_docs_dir: Option<String>,
+    agent: Option<String>,
+    /// Directory of the generated files, used to re-base relative links
+    output_dir: Option<PathBuf>,
 }
 
 impl MarkdownMerger {
@@ -21,6 +50,8 @@ impl MarkdownMerger {
         Self {
             config,
             base_docs_dir: None,
+            agent: None,
+            output_dir: None,
         }
     }
 
@@ -29,43 +60,106 @@ impl MarkdownMerger {
         Self {
             config,
             base_docs_dir: Some(base_docs_dir),
+            agent: None,
+            output_dir: None,
+        }
+    }
+
+    /// Create a new Markdown merger for specified agent
+    /// Uses the agent's effective docs sources and skips docs not targeted at the agent
+    pub fn new_for_agent(config: AIContextConfig, agent: &str) -> Self {
+        Self {
+            config,
+            base_docs_dir: None,
+            agent: Some(agent.to_string()),
+            output_dir: None,
         }
     }
 
+    /// Set the directory the docs are generated into
+    /// With rewrite_links enabled, relative links are re-based from each source doc to it
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(output_dir.into());
+        self
+    }
+
     /// Get effective base docs directory
     fn get_effective_base_docs_dir(&self) -> &str {
-        self.base_docs_dir
-            .as_deref()
-            .unwrap_or(&self.config.base_docs_dir)
+        match (&self.base_docs_dir, &self.agent) {
+            (Some(base_docs_dir), _) => base_docs_dir,
+            (None, Some(agent)) => self.config.get_effective_base_docs_dir(agent),
+            (None, None) => &self.config.base_docs_dir,
+        }
     }
 
-    /// Merge all Markdown files under docs (includes filename headers for backward compatibility)
-    pub async fn merge_all(&self) -> Result<String> {
-        let base_dir = self.get_effective_base_docs_dir();
-        let docs_dir = Path::new(base_dir);
+    /// Resolve the directories docs are loaded from, in merge order, with their path prefixes
+    /// An explicit base directory wins; otherwise docs_sources (globs expanded) or base_docs_dir,
+    /// followed by vendored packs
+    fn resolve_docs_sources(&self, agent: Option<&str>) -> Result<Vec<(PathBuf, Option<String>)>> {
+        let sources = match self.base_docs_dir {
+            Some(_) => None,
+            None => self.config.get_effective_docs_sources(agent),
+        };
+        let mut resolved = Vec::new();
+        if sources.is_none() {
+            resolved.push((PathBuf::from(self.get_effective_base_docs_dir()), None));
+        }
 
-        // Return empty string if directory doesn't exist
-        if !docs_dir.exists() {
-            return Ok(String::new());
+        for source in sources.unwrap_or_default() {
+            let path = source.path();
+            let prefix = source
+                .prefix()
+                .map(|prefix| prefix.trim_matches('/').to_string())
+                .filter(|prefix| !prefix.is_empty());
+
+            if path.contains(['*', '?', '[']) {
+                let mut dirs: Vec<PathBuf> = glob::glob(path)
+                    .with_context(|| format!("Invalid docs source pattern: {path}"))?
+                    .filter_map(|entry| entry.ok())
+                    .filter(|dir| dir.is_dir())
+                    .collect();
+                if dirs.is_empty() {
+                    eprintln!("Warning: docs source '{path}' matches no directories");
+                }
+                dirs.sort();
+                resolved.extend(dirs.into_iter().map(|dir| (dir, prefix.clone())));
+            } else if Path::new(path).is_dir() {
+                resolved.push((PathBuf::from(path), prefix));
+            } else {
+                eprintln!("Warning: docs source '{path}' does not exist");
+            }
+        }
+
+        // Vendored packs follow the configured sources
+        if self.base_docs_dir.is_none() {
+            let manager = PackManager::new(self.config.get_packs_root());
+            for pack in self.config.packs.iter().flatten() {
+                let docs_dir = manager.docs_dir(pack)?;
+                if docs_dir.is_dir() {
+                    let prefix = pack.get_prefix().trim_matches('/');
+                    let prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+                    resolved.push((docs_dir, prefix));
+                } else {
+                    eprintln!(
+                        "Warning: pack '{}' is not vendored; run `aicm packs update`",
+                        pack.name
+                    );
+                }
+            }
         }
+        Ok(resolved)
+    }
 
-        let markdown_files = self.find_markdown_files(docs_dir).await?;
+    /// Merge all Markdown files under docs (includes filename headers for backward compatibility)
+    pub async fn merge_all(&self) -> Result<String> {
+        let docs = self.load_documents(self.agent.as_deref()).await?;
         let mut merged_content = String::new();
 
         // Always include filename headers for backward compatibility
-        for file_path in markdown_files {
-            if let Ok(content) = fs::read_to_string(&file_path).await {
-                // Add filename as header
-                let base_dir = self.get_effective_base_docs_dir();
-                let relative_path = file_path
-                    .strip_prefix(base_dir)
-                    .unwrap_or(&file_path)
-                    .to_string_lossy()
-                    .replace('\\', "/"); // Normalize path separators for cross-platform compatibility
-
-                let trimmed_content = content.trim();
-                merged_content.push_str(&format!("# {relative_path}\n\n{trimmed_content}\n\n"));
-            }
+        let heading_mode = self.config.get_heading_mode();
+        for doc in docs {
+            let section = format_doc_section(&doc.path, &doc.content, &heading_mode);
+            merged_content.push_str(&format!("{section}\n\n"));
         }
 
         Ok(merged_content.trim().to_string())
@@ -73,15 +167,8 @@ impl MarkdownMerger {
 
     /// Merge all Markdown files under docs (agent name specified version)
     pub async fn merge_all_with_options(&self, agent: Option<&str>) -> Result<String> {
-        let base_dir = self.get_effective_base_docs_dir();
-        let docs_dir = Path::new(base_dir);
-
-        // Return empty string if directory doesn't exist
-        if !docs_dir.exists() {
-            return Ok(String::new());
-        }
-
-        let markdown_files = self.find_markdown_files(docs_dir).await?;
+        let agent = agent.or(self.agent.as_deref());
+        let docs = self.load_documents(agent).await?;
         let mut merged_content = String::new();
 
         // Get include_filenames setting
@@ -91,76 +178,369 @@ impl MarkdownMerger {
             self.config.include_filenames.unwrap_or(false)
         };
 
-        for file_path in markdown_files {
-            if let Ok(content) = fs::read_to_string(&file_path).await {
-                if include_filenames {
-                    // Add filename as header
-                    let relative_path = file_path
-                        .strip_prefix(&self.config.base_docs_dir)
-                        .unwrap_or(&file_path)
-                        .to_string_lossy()
-                        .replace('\\', "/"); // Normalize path separators for cross-platform compatibility
-
-                    let trimmed_content = content.trim();
-                    merged_content.push_str(&format!("# {relative_path}\n\n{trimmed_content}\n\n"));
-                } else {
-                    // Add content only without filename header
-                    let trimmed_content = content.trim();
-                    merged_content.push_str(&format!("{trimmed_content}\n\n"));
-                }
+        let heading_mode = self.config.get_heading_mode();
+        for doc in docs {
+            let trimmed_content = doc.content.trim();
+            if include_filenames {
+                // Add filename as header (doc headings normalized per heading_mode)
+                let section = format_doc_section(&doc.path, &doc.content, &heading_mode);
+                merged_content.push_str(&format!("{section}\n\n"));
+            } else {
+                // Add content only without filename header
+                merged_content.push_str(&format!("{trimmed_content}\n\n"));
             }
         }
 
-        Ok(merged_content.trim().to_string())
+        let merged_content = merged_content.trim();
+        if agent.is_some_and(|agent_name| self.config.get_effective_toc(agent_name)) {
+            return Ok(insert_toc(merged_content));
+        }
+        Ok(merged_content.to_string())
     }
 
     /// For split: get individual file contents
     pub async fn get_individual_files(&self) -> Result<Vec<(String, String)>> {
-        let base_dir = self.get_effective_base_docs_dir();
-        let docs_dir = Path::new(base_dir);
+        Ok(self
+            .get_documents()
+            .await?
+            .into_iter()
+            .map(|doc| (doc.path, doc.content))
+            .collect())
+    }
 
+    /// For split: get individual documents with their frontmatter metadata
+    pub async fn get_documents(&self) -> Result<Vec<SourceDoc>> {
+        self.load_documents(self.agent.as_deref()).await
+    }
+
+    /// For split: get documents with large docs split into one document per section
+    /// Section paths add the heading slug to the doc name (`design.md` -> `design-auth.md`)
+    pub async fn get_split_documents(&self) -> Result<Vec<SourceDoc>> {
+        let docs = self.get_documents().await?;
+        let Some(split) = &self.config.split_sections else {
+            return Ok(docs);
+        };
+
+        let mut split_docs = Vec::with_capacity(docs.len());
+        for doc in docs {
+            let sections = if estimate_tokens(&doc.content) > split.get_min_tokens() {
+                split_sections(&doc.content, split.get_heading_level())
+            } else {
+                Vec::new()
+            };
+            if sections.iter().all(|section| section.heading.is_none()) {
+                split_docs.push(doc);
+                continue;
+            }
+
+            let stem = self.config.strip_source_extension(&doc.path);
+            let extension = self
+                .config
+                .match_source_extension(&doc.path)
+                .unwrap_or("md");
+            for section in sections {
+                let path = match section.heading {
+                    Some(_) => format!("{stem}-{}.{extension}", section.slug),
+                    None => doc.path.clone(),
+                };
+                split_docs.push(SourceDoc {
+                    path,
+                    content: section.content,
+                    frontmatter: doc.frontmatter.clone(),
+                });
+            }
+        }
+        Ok(split_docs)
+    }
+
+    /// List source doc files of every docs source as (file path, doc path) without loading them
+    pub async fn find_source_files(&self) -> Result<Vec<(PathBuf, String)>> {
+        let agent = self.agent.as_deref();
+        let mut files = Vec::new();
+        for (docs_dir, prefix) in self.resolve_docs_sources(agent)? {
+            if !docs_dir.exists() {
+                continue;
+            }
+            let filter = self.build_doc_filter(&docs_dir, agent).await?;
+            for file_path in self.find_markdown_files(&docs_dir, &filter).await? {
+                let relative_path = doc_path(&docs_dir, prefix.as_deref(), &file_path);
+                files.push((file_path, relative_path));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Load documents from every docs source, evaluating conditional blocks for the agent, expanding includes,
+    /// substituting template variables, parsing frontmatter and skipping docs not targeted at the agent
+    /// Docs are returned in output order; on path collisions between sources the first source wins
+    async fn load_documents(&self, agent: Option<&str>) -> Result<Vec<SourceDoc>> {
+        let mut docs = Vec::new();
+        let mut origins: HashMap<String, PathBuf> = HashMap::new();
+
+        for (index, (docs_dir, prefix)) in self.resolve_docs_sources(agent)?.into_iter().enumerate()
+        {
+            for doc in self
+                .load_source(&docs_dir, prefix.as_deref(), agent)
+                .await?
+            {
+                if let Some(first) = origins.get(&doc.path) {
+                    eprintln!(
+                        "Warning: {} in docs source {} collides with docs source {}; keeping the first",
+                        doc.path,
+                        docs_dir.display(),
+                        first.display()
+                    );
+                    continue;
+                }
+                origins.insert(doc.path.clone(), docs_dir.clone());
+                docs.push((index, doc));
+            }
+        }
+
+        self.sort_documents(&mut docs)?;
+        Ok(docs.into_iter().map(|(_, doc)| doc).collect())
+    }
+
+    /// Load documents of a single docs directory, adding `prefix` to their paths
+    async fn load_source(
+        &self,
+        docs_dir: &Path,
+        prefix: Option<&str>,
+        agent: Option<&str>,
+    ) -> Result<Vec<SourceDoc>> {
+        // Return no documents if directory doesn't exist
         if !docs_dir.exists() {
             return Ok(Vec::new());
         }
 
-        let markdown_files = self.find_markdown_files(docs_dir).await?;
-        let mut files = Vec::new();
+        let filter = self.build_doc_filter(docs_dir, agent).await?;
+        let markdown_files = self.find_markdown_files(docs_dir, &filter).await?;
+        let project_root = project_root_for(self.config.config_dir.as_deref(), docs_dir)?;
+        let expander = IncludeExpander::new(&project_root, agent)?
+            .with_source_encoding(self.config.source_encoding.as_deref())
+            .with_normalize_line_endings(self.config.get_normalize_line_endings())
+            .with_max_file_size(self.config.get_max_file_size());
+        let template = if self.config.is_templating_enabled() {
+            let variables = self.config.variables.clone().unwrap_or_default();
+            Some(TemplateContext::new(agent, &variables, &project_root)?)
+        } else {
+            None
+        };
+        let excluded_sections = SectionSelector::new(&match agent {
+            Some(agent_name) => self.config.get_effective_exclude_sections(agent_name),
+            None => self.config.exclude_sections.clone().unwrap_or_default(),
+        })?;
+        let mut docs = Vec::new();
 
         for file_path in markdown_files {
-            if let Ok(content) = fs::read_to_string(&file_path).await {
-                let base_dir = self.get_effective_base_docs_dir();
-                let relative_path = file_path
-                    .strip_prefix(base_dir)
-                    .unwrap_or(&file_path)
-                    .to_string_lossy()
-                    .replace('\\', "/"); // Normalize path separators for cross-platform compatibility
+            let relative_path = doc_path(docs_dir, prefix, &file_path);
 
-                files.push((relative_path, content));
+            // Unreadable or undecodable docs are reported and skipped
+            let bytes = match fs::read(&file_path).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    eprintln!("Warning: skipping {relative_path}: failed to read: {e}");
+                    continue;
+                }
+            };
+            if looks_binary(&bytes) {
+                eprintln!("Warning: skipping {relative_path}: binary file");
+                continue;
+            }
+            let decoded = match decode_source(
+                &bytes,
+                self.config.source_encoding.as_deref(),
+                self.config.get_normalize_line_endings(),
+            ) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    eprintln!("Warning: skipping {relative_path}: {e:#}");
+                    continue;
+                }
+            };
+            for warning in &decoded.warnings {
+                eprintln!("Warning: {relative_path}: {warning}");
+            }
+
+            let mut content = decoded.content;
+            if self.config.match_source_extension(&relative_path) == Some("mdx") {
+                content = strip_mdx_esm(&content);
+            }
+
+            let raw_content = evaluate_conditionals(&content, agent).with_context(|| {
+                format!("Failed to evaluate conditional blocks in {relative_path}")
+            })?;
+            let mut raw_content = expander.expand(&file_path, &raw_content)?;
+            if let Some(template) = &template {
+                raw_content = template.render(&raw_content).with_context(|| {
+                    format!("Failed to render template variables in {relative_path}")
+                })?;
+            }
+            if let Some(reason) = ignored_frontmatter_block(&raw_content) {
+                eprintln!(
+                    "Warning: {relative_path}: leading '---' block is not frontmatter ({reason}); keeping it as content"
+                );
+            }
+            let (frontmatter, body) = split_frontmatter(&raw_content)
+                .with_context(|| format!("Failed to parse frontmatter of {relative_path}"))?;
+            let frontmatter = frontmatter.unwrap_or_default();
+
+            if let Some(agent_name) = agent {
+                if !frontmatter.targets_agent(agent_name) {
+                    continue;
+                }
             }
+
+            let mut content = excluded_sections.drop_from(body);
+            if let (Some(output_dir), true) = (&self.output_dir, self.config.get_rewrite_links()) {
+                let source_dir = file_path.parent().unwrap_or(docs_dir);
+                let rewritten = rewrite_links(&content, source_dir, output_dir);
+                for target in &rewritten.unresolved {
+                    eprintln!("Warning: {relative_path}: link target '{target}' does not exist");
+                }
+                content = rewritten.content;
+            }
+
+            docs.push(SourceDoc {
+                path: relative_path,
+                content,
+                frontmatter,
+            });
         }
 
-        Ok(files)
+        Ok(docs)
     }
 
-    /// Recursively search for .md files from specified directory
-    async fn find_markdown_files(&self, dir: &Path) -> Result<Vec<std::path::PathBuf>> {
-        use std::collections::VecDeque;
+    /// Sort docs by the config `order:` list, then docs source, then frontmatter `order`, then path
+    /// Each doc is paired with the index of the docs source it was loaded from
+    fn sort_documents(&self, docs: &mut [(usize, SourceDoc)]) -> Result<()> {
+        let patterns = self
+            .config
+            .get_order_patterns()
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).with_context(|| format!("Invalid order pattern: {pattern}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        docs.sort_by_cached_key(|(source, doc)| {
+            let rank = patterns
+                .iter()
+                .position(|pattern| pattern.matches(&doc.path))
+                .unwrap_or(patterns.len());
+            let order = doc.frontmatter.order;
+            (rank, *source, order.is_none(), order, doc.path.clone())
+        });
+        Ok(())
+    }
+
+    /// Build the doc filter from include/exclude settings and the docs directory's .aicmignore
+    async fn build_doc_filter(&self, docs_dir: &Path, agent: Option<&str>) -> Result<DocFilter> {
+        let (include, exclude) = match agent {
+            Some(agent_name) => (
+                self.config
+                    .get_effective_include_patterns(agent_name)
+                    .to_vec(),
+                self.config.get_effective_exclude_patterns(agent_name),
+            ),
+            None => (
+                self.config.include.clone().unwrap_or_default(),
+                self.config.exclude.clone().unwrap_or_default(),
+            ),
+        };
+
+        let ignore_path = docs_dir.join(IGNORE_FILE_NAME);
+        let ignore = match fs::read_to_string(&ignore_path).await {
+            Ok(content) => IgnoreRules::parse(&content)?,
+            Err(_) => IgnoreRules::default(),
+        };
+
+        DocFilter::new(&include, &exclude, ignore)
+    }
+
+    /// Recursively search for source doc files from specified directory, skipping filtered paths
+    /// Symlink cycles and entries beyond max_depth/max_file_size are skipped with a warning
+    async fn find_markdown_files(
+        &self,
+        dir: &Path,
+        filter: &DocFilter,
+    ) -> Result<Vec<std::path::PathBuf>> {
+        use std::collections::{HashSet, VecDeque};
+
+        let symlinks = self.config.get_symlink_policy();
+        let max_depth = self.config.get_max_depth();
+        let max_file_size = self.config.get_max_file_size();
+        let root = fs::canonicalize(dir).await?;
 
         let mut files = Vec::new();
+        let mut visited = HashSet::new();
+        if let Some(id) = entry_id(&root, &fs::metadata(&root).await?) {
+            visited.insert(id);
+        }
         let mut dirs_to_process = VecDeque::new();
-        dirs_to_process.push_back(dir.to_path_buf());
+        dirs_to_process.push_back((dir.to_path_buf(), 0));
 
-        while let Some(current_dir) = dirs_to_process.pop_front() {
+        while let Some((current_dir, depth)) = dirs_to_process.pop_front() {
             let mut entries = fs::read_dir(&current_dir).await?;
 
             while let Some(entry) = entries.next_entry().await? {
                 let path = entry.path();
+                let relative_path = path
+                    .strip_prefix(dir)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let skip = |reason: &str| {
+                    eprintln!("Warning: skipping {}: {reason}", path.display());
+                };
 
-                if path.is_dir() {
-                    // Add to processing queue if it's a directory
-                    dirs_to_process.push_back(path);
-                } else if path.extension().and_then(|s| s.to_str()) == Some("md") {
-                    // Add to list if it's a .md file
+                let metadata = if entry.file_type().await?.is_symlink() {
+                    if symlinks == SymlinkPolicy::Skip {
+                        skip("symbolic link (symlinks: skip)");
+                        continue;
+                    }
+                    let Ok(target) = fs::canonicalize(&path).await else {
+                        skip("broken symbolic link");
+                        continue;
+                    };
+                    if symlinks == SymlinkPolicy::FollowWithinRoot && !target.starts_with(&root) {
+                        skip("symbolic link points outside the docs directory");
+                        continue;
+                    }
+                    fs::metadata(&path).await?
+                } else {
+                    entry.metadata().await?
+                };
+
+                if metadata.is_dir() {
+                    // Add to processing queue if it's a directory that is not skipped
+                    if filter.skips_dir(&relative_path) {
+                        continue;
+                    }
+                    if depth >= max_depth {
+                        skip(&format!("deeper than max_depth ({max_depth})"));
+                        continue;
+                    }
+                    if entry_id(&path, &metadata).is_some_and(|id| !visited.insert(id)) {
+                        skip("directory already scanned (symbolic link cycle)");
+                        continue;
+                    }
+                    dirs_to_process.push_back((path, depth + 1));
+                } else if self.config.match_source_extension(&relative_path).is_some()
+                    && filter.accepts_file(&relative_path)
+                {
+                    // Add to list if it's an accepted file with a source extension
+                    if metadata.len() > max_file_size {
+                        skip(&format!(
+                            "{} bytes exceeds max_file_size ({max_file_size})",
+                            metadata.len()
+                        ));
+                        continue;
+                    }
+                    if entry_id(&path, &metadata).is_some_and(|id| !visited.insert(id)) {
+                        skip("file already loaded through another path");
+                        continue;
+                    }
                     files.push(path);
                 }
             }
@@ -172,6 +552,36 @@ impl MarkdownMerger {
     }
 }
 
+/// Doc path of a file relative to its docs directory, with the source prefix (always uses '/')
+fn doc_path(docs_dir: &Path, prefix: Option<&str>, file_path: &Path) -> String {
+    let relative_path = file_path
+        .strip_prefix(docs_dir)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .replace('\\', "/"); // Normalize path separators for cross-platform compatibility
+    match prefix {
+        Some(prefix) => format!("{prefix}/{relative_path}"),
+        None => relative_path,
+    }
+}
+
+/// Identity of a scanned file or directory, used to detect symbolic link cycles
+#[cfg(unix)]
+type EntryId = (u64, u64);
+#[cfg(not(unix))]
+type EntryId = PathBuf;
+
+#[cfg(unix)]
+fn entry_id(_path: &Path, metadata: &std::fs::Metadata) -> Option<EntryId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn entry_id(path: &Path, _metadata: &std::fs::Metadata) -> Option<EntryId> {
+    std::fs::canonicalize(path).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,6 +596,7 @@ mod tests {
             include_filenames: None,
             base_docs_dir: base_dir.to_string(),
             agents: AgentConfig::default(),
+            ..Default::default()
         }
     }
 
@@ -334,4 +745,595 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].0, "test.md");
     }
+
+    #[tokio::test]
+    async fn test_frontmatter_is_stripped_and_parsed() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("rust.md"),
+            "---\nglobs: [\"**/*.rs\"]\n---\n\n# Rust\nRust rules",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+        let merger = MarkdownMerger::new(config);
+
+        let docs = merger.get_documents().await.unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].content, "# Rust\nRust rules");
+        assert_eq!(docs[0].frontmatter.globs, Some(vec!["**/*.rs".to_string()]));
+
+        let merged = merger.merge_all().await.unwrap();
+        assert!(!merged.contains("globs:"));
+        assert!(!merged.contains("---"));
+    }
+
+    #[tokio::test]
+    async fn test_frontmatter_agents_filter() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("claude-only.md"),
+            "---\nagents: [claude]\n---\nClaude content",
+        )
+        .await
+        .unwrap();
+        fs::write(docs_path.join("common.md"), "Common content")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+
+        let merger = MarkdownMerger::new_for_agent(config.clone(), "claude");
+        let merged = merger.merge_all_with_options(None).await.unwrap();
+        assert!(merged.contains("Claude content"));
+        assert!(merged.contains("Common content"));
+
+        let merger = MarkdownMerger::new_for_agent(config.clone(), "cursor");
+        let files = merger.get_individual_files().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "common.md");
+
+        // Merger without agent does not filter
+        let merger = MarkdownMerger::new(config);
+        assert_eq!(merger.get_individual_files().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_frontmatter_reports_file() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("broken.md"), "---\norder: first\n---\nBody")
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+        let merger = MarkdownMerger::new(config);
+
+        let error = merger.get_documents().await.unwrap_err();
+        assert!(error.to_string().contains("broken.md"));
+    }
+
+    #[tokio::test]
+    async fn test_leading_horizontal_rule_block_is_content() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+        let content = "---\nIntro between rules: see below.\nMore intro [link\n---\n\n# Title\n";
+        fs::write(docs_path.join("intro.md"), content)
+            .await
+            .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+        let docs = MarkdownMerger::new(config).get_documents().await.unwrap();
+        assert_eq!(docs[0].content, content);
+        assert_eq!(docs[0].frontmatter, DocFrontmatter::default());
+    }
+
+    #[tokio::test]
+    async fn test_conditional_blocks_per_agent() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("testing.md"),
+            "# Testing\n<!-- aicm:if agent=claude -->\nUse the /test slash command\n<!-- aicm:else -->\nRun cargo test\n<!-- aicm:endif -->\n",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+
+        let merger = MarkdownMerger::new_for_agent(config.clone(), "claude");
+        let merged = merger.merge_all_with_options(None).await.unwrap();
+        assert_eq!(merged, "# Testing\nUse the /test slash command");
+
+        let merger = MarkdownMerger::new_for_agent(config, "cursor");
+        let docs = merger.get_documents().await.unwrap();
+        assert_eq!(docs[0].content, "# Testing\nRun cargo test\n");
+    }
+
+    #[tokio::test]
+    async fn test_unbalanced_conditional_reports_file_and_line() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("broken.md"),
+            "# Title\n<!-- aicm:if agent=claude -->\nBody\n",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+        let merger = MarkdownMerger::new_for_agent(config, "claude");
+
+        let error = merger.get_documents().await.unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains("broken.md"));
+        assert!(message.contains("line 2: aicm:if is not closed"));
+    }
+
+    #[tokio::test]
+    async fn test_include_directives_are_expanded() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_path).await.unwrap();
+        fs::write(
+            temp_dir.path().join("security.md"),
+            "<!-- aicm:if agent=claude -->\nNever commit secrets\n<!-- aicm:endif -->\n",
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("guide.md"),
+            "# Guide\n<!-- aicm:include ../security.md -->\n",
+        )
+        .await
+        .unwrap();
+
+        // Docs dir outside the current directory: includes must stay inside it
+        let config = create_test_config(&docs_path.to_string_lossy());
+        let merger = MarkdownMerger::new_for_agent(config, "claude");
+        let error = merger.get_documents().await.unwrap_err();
+        assert!(format!("{error:#}").contains("outside the project root"));
+
+        fs::rename(
+            temp_dir.path().join("security.md"),
+            docs_path.join("security.md"),
+        )
+        .await
+        .unwrap();
+        fs::write(
+            docs_path.join("guide.md"),
+            "# Guide\n<!-- aicm:include security.md -->\n",
+        )
+        .await
+        .unwrap();
+
+        let config = create_test_config(&docs_path.to_string_lossy());
+        let merger = MarkdownMerger::new_for_agent(config, "claude");
+        let docs = merger.get_documents().await.unwrap();
+        assert_eq!(docs[0].path, "guide.md");
+        assert_eq!(docs[0].content, "# Guide\nNever commit secrets\n");
+    }
+
+    #[tokio::test]
+    async fn test_include_root_is_config_dir() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path().join("docs");
+        let shared_path = temp_dir.path().join("shared");
+        fs::create_dir_all(&docs_path).await.unwrap();
+        fs::create_dir_all(&shared_path).await.unwrap();
+        fs::write(shared_path.join("security.md"), "Never commit secrets\n")
+            .await
+            .unwrap();
+        fs::write(
+            docs_path.join("guide.md"),
+            "# Guide\n<!-- aicm:include ../shared/security.md -->\n",
+        )
+        .await
+        .unwrap();
+
+        // The config lives outside the current directory, next to docs/ and shared/
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.config_dir = Some(temp_dir.path().to_path_buf());
+        let merger = MarkdownMerger::new_for_agent(config, "claude");
+        let docs = merger.get_documents().await.unwrap();
+        assert_eq!(docs[0].content, "# Guide\nNever commit secrets\n");
+    }
+
+    #[tokio::test]
+    async fn test_template_variables_are_substituted() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(
+            docs_path.join("guide.md"),
+            "---\ndescription: Rules for {{ agent.name }}\n---\nBranch: {{ default_branch }}\nLiteral: \\{{ x }}\n",
+        )
+        .await
+        .unwrap();
+
+        // Disabled by default: content is left untouched
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        let merger = MarkdownMerger::new_for_agent(config.clone(), "cursor");
+        let docs = merger.get_documents().await.unwrap();
+        assert!(docs[0].content.contains("{{ default_branch }}"));
+
+        config.templating = Some(true);
+        config.variables = Some(std::collections::BTreeMap::from([(
+            "default_branch".to_string(),
+            serde_yaml::Value::String("main".to_string()),
+        )]));
+        let merger = MarkdownMerger::new_for_agent(config.clone(), "cursor");
+        let docs = merger.get_documents().await.unwrap();
+        assert_eq!(
+            docs[0].frontmatter.description,
+            Some("Rules for cursor".to_string())
+        );
+        assert_eq!(docs[0].content, "Branch: main\nLiteral: {{ x }}\n");
+
+        // Undefined variables are an error naming the file
+        config.variables = None;
+        let merger = MarkdownMerger::new_for_agent(config, "cursor");
+        let error = merger.get_documents().await.unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains("guide.md"));
+        assert!(message.contains("undefined template variable 'default_branch'"));
+    }
+
+    #[tokio::test]
+    async fn test_documents_follow_configured_order() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::create_dir_all(docs_path.join("guides")).await.unwrap();
+        for (path, content) in [
+            ("architecture.md", "Architecture"),
+            ("guides/testing.md", "Testing"),
+            ("guides/style.md", "---\norder: 1\n---\nStyle"),
+            ("overview.md", "Overview"),
+            ("notes.md", "Notes"),
+            ("conventions.md", "---\norder: -1\n---\nConventions"),
+        ] {
+            fs::write(docs_path.join(path), content).await.unwrap();
+        }
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.order = Some(vec!["overview.md".to_string(), "guides/*".to_string()]);
+
+        let merger = MarkdownMerger::new(config);
+        let paths: Vec<String> = merger
+            .get_documents()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|doc| doc.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "overview.md",
+                "guides/style.md",
+                "guides/testing.md",
+                "conventions.md",
+                "architecture.md",
+                "notes.md",
+            ]
+        );
+
+        let merged = merger.merge_all_with_options(None).await.unwrap();
+        assert!(merged.starts_with("Overview\n\nStyle\n\nTesting"));
+    }
+
+    #[tokio::test]
+    async fn test_documents_from_multiple_docs_sources() {
+        use crate::types::{DocsSource, DocsSourceConfig};
+
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        for (path, content) in [
+            ("common/style.md", "Common style"),
+            ("common/security.md", "Security"),
+            ("rust/style.md", "Rust style"),
+            ("packs/a/docs/a.md", "Pack A"),
+            ("packs/b/docs/style.md", "Pack style"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+            fs::write(path, content).await.unwrap();
+        }
+
+        let mut config = create_test_config(&root.join("missing").to_string_lossy());
+        config.docs_sources = Some(vec![
+            DocsSource::Simple(root.join("common").to_string_lossy().to_string()),
+            DocsSource::Advanced(DocsSourceConfig {
+                path: root.join("rust").to_string_lossy().to_string(),
+                prefix: Some("rust/".to_string()),
+            }),
+            // Glob sources: docs/style.md collides with common/style.md
+            DocsSource::Simple(format!("{}/packs/*/docs", root.to_string_lossy())),
+        ]);
+
+        let merger = MarkdownMerger::new_for_agent(config, "claude");
+        let docs = merger.get_documents().await.unwrap();
+        let docs: Vec<(&str, &str)> = docs
+            .iter()
+            .map(|doc| (doc.path.as_str(), doc.content.as_str()))
+            .collect();
+
+        // Sources merge in configured order; the first source wins on collisions
+        assert_eq!(
+            docs,
+            vec![
+                ("security.md", "Security"),
+                ("style.md", "Common style"),
+                ("rust/style.md", "Rust style"),
+                ("a.md", "Pack A"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_docs_are_decoded_and_invalid_utf8_is_skipped() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        fs::write(docs_path.join("bom.md"), b"\xEF\xBB\xBF# BOM\r\nText\r\n")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("latin1.md"), b"caf\xe9\n")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        let docs = MarkdownMerger::new(config.clone())
+            .get_documents()
+            .await
+            .unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, "bom.md");
+        assert_eq!(docs[0].content, "# BOM\nText\n");
+
+        config.source_encoding = Some("latin1".to_string());
+        let docs = MarkdownMerger::new(config).get_documents().await.unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[1].content, "café\n");
+    }
+
+    #[tokio::test]
+    async fn test_relative_links_are_rewritten_for_output_dir() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path().join("ai-context");
+        fs::create_dir_all(docs_path.join("diagrams"))
+            .await
+            .unwrap();
+        fs::write(docs_path.join("diagrams/arch.png"), "")
+            .await
+            .unwrap();
+        fs::write(
+            docs_path.join("guide.md"),
+            "![arch](./diagrams/arch.png) [gone](missing.md)\n<!-- aicm:include parts/usage.md -->\n",
+        )
+        .await
+        .unwrap();
+        fs::create_dir_all(docs_path.join("parts")).await.unwrap();
+        fs::write(
+            docs_path.join("parts/usage.md"),
+            "[flow](../diagrams/arch.png)\n",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        let output_dir = temp_dir.path().join(".cursor/rules");
+
+        // Disabled by default
+        let docs = MarkdownMerger::new(config.clone())
+            .with_output_dir(&output_dir)
+            .get_documents()
+            .await
+            .unwrap();
+        assert_eq!(
+            docs[0].content,
+            "![arch](./diagrams/arch.png) [gone](missing.md)\n[flow](diagrams/arch.png)\n"
+        );
+
+        config.rewrite_links = Some(true);
+        let docs = MarkdownMerger::new(config)
+            .with_output_dir(&output_dir)
+            .get_documents()
+            .await
+            .unwrap();
+        assert_eq!(
+            docs[0].content,
+            "![arch](../../ai-context/diagrams/arch.png) [gone](missing.md)\n[flow](../../ai-context/diagrams/arch.png)\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlink_policies_and_cycles() {
+        use crate::types::SymlinkPolicy;
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path().join("docs");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(docs_path.join("guides")).await.unwrap();
+        fs::create_dir_all(&outside).await.unwrap();
+        fs::write(docs_path.join("guides/testing.md"), "Testing")
+            .await
+            .unwrap();
+        fs::write(outside.join("shared.md"), "Shared")
+            .await
+            .unwrap();
+        // guides/loop -> docs creates a cycle
+        symlink(&docs_path, docs_path.join("guides/loop")).unwrap();
+        symlink(&outside, docs_path.join("external")).unwrap();
+
+        let paths = |config: AIContextConfig| async move {
+            MarkdownMerger::new(config)
+                .get_documents()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|doc| doc.path)
+                .collect::<Vec<_>>()
+        };
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        assert_eq!(
+            paths(config.clone()).await,
+            vec!["external/shared.md", "guides/testing.md"]
+        );
+
+        config.symlinks = Some(SymlinkPolicy::FollowWithinRoot);
+        assert_eq!(paths(config.clone()).await, vec!["guides/testing.md"]);
+
+        config.symlinks = Some(SymlinkPolicy::Skip);
+        assert_eq!(paths(config).await, vec!["guides/testing.md"]);
+    }
+
+    #[tokio::test]
+    async fn test_depth_size_and_binary_limits() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+        fs::create_dir_all(docs_path.join("a/b")).await.unwrap();
+        fs::write(docs_path.join("top.md"), "Top").await.unwrap();
+        fs::write(docs_path.join("a/b/deep.md"), "Deep")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("large.md"), "x".repeat(64))
+            .await
+            .unwrap();
+        fs::write(docs_path.join("image.md"), b"\x89PNG\x00\x00")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.max_depth = Some(1);
+        config.max_file_size = Some(32);
+
+        let paths: Vec<String> = MarkdownMerger::new(config)
+            .get_documents()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|doc| doc.path)
+            .collect();
+        assert_eq!(paths, vec!["top.md"]);
+    }
+
+    #[tokio::test]
+    async fn test_excluded_sections_are_dropped_per_agent() {
+        use crate::types::{CursorAgentConfig, CursorConfig};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+        fs::write(
+            docs_path.join("guide.md"),
+            "# Guide\n\n## Human onboarding\n\nWelcome!\n\n### Laptop\n\nSetup.\n\n## Rules\n\n| a | b |\n|---|---|\n\n```md\n## Human onboarding\n```\n\n## Changelog\n\n<details>\n## Not a heading\n</details>\n",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.exclude_sections = Some(vec!["human ONBOARDING".to_string()]);
+        config.agents.cursor = CursorConfig::Advanced(CursorAgentConfig {
+            enabled: true,
+            include_filenames: None,
+            output_mode: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: Some(vec!["Change*".to_string()]),
+            split_config: None,
+        });
+
+        let docs = MarkdownMerger::new_for_agent(config.clone(), "claude")
+            .get_documents()
+            .await
+            .unwrap();
+        assert_eq!(
+            docs[0].content,
+            "# Guide\n\n## Rules\n\n| a | b |\n|---|---|\n\n```md\n## Human onboarding\n```\n\n## Changelog\n\n<details>\n## Not a heading\n</details>\n"
+        );
+
+        let docs = MarkdownMerger::new_for_agent(config, "cursor")
+            .get_documents()
+            .await
+            .unwrap();
+        assert_eq!(
+            docs[0].content,
+            "# Guide\n\n## Rules\n\n| a | b |\n|---|---|\n\n```md\n## Human onboarding\n```\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_docs_are_filtered_by_globs_and_aicmignore() {
+        use crate::types::{ClaudeAgentConfig, ClaudeConfig};
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+
+        for dir in ["drafts", "node_modules/pkg", ".git", "guides"] {
+            fs::create_dir_all(docs_path.join(dir)).await.unwrap();
+        }
+        for path in [
+            "overview.md",
+            "README.md",
+            "drafts/idea.md",
+            "node_modules/pkg/README.md",
+            ".git/notes.md",
+            "guides/testing.md",
+            "guides/testing.human.md",
+        ] {
+            fs::write(docs_path.join(path), path).await.unwrap();
+        }
+        fs::write(docs_path.join(".aicmignore"), "drafts/\n/README.md\n")
+            .await
+            .unwrap();
+
+        let mut config = create_test_config(&docs_path.to_string_lossy());
+        config.exclude = Some(vec!["*.human.md".to_string()]);
+
+        let paths = |docs: Vec<SourceDoc>| -> Vec<String> {
+            docs.into_iter().map(|doc| doc.path).collect()
+        };
+
+        let merger = MarkdownMerger::new(config.clone());
+        assert_eq!(
+            paths(merger.get_documents().await.unwrap()),
+            vec!["guides/testing.md", "overview.md"]
+        );
+
+        // Agent include globs override the global setting
+        config.agents.claude = ClaudeConfig::Advanced(ClaudeAgentConfig {
+            enabled: true,
+            output_mode: None,
+            include_filenames: None,
+            base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: Some(vec!["guides/*".to_string()]),
+            exclude: None,
+            exclude_sections: None,
+            import_files: Vec::new(),
+        });
+        let merger = MarkdownMerger::new_for_agent(config, "claude");
+        assert_eq!(
+            paths(merger.get_documents().await.unwrap()),
+            vec!["guides/testing.md"]
+        );
+    }
 }
@@ -17,6 +17,12 @@ mod include_filenames_tests {
                 output_mode: Some(OutputMode::Merged),
                 include_filenames: Some(include_filenames),
                 base_docs_dir: None,
+                docs_sources: None,
+                max_tokens: None,
+                toc: None,
+                include: None,
+                exclude: None,
+                exclude_sections: None,
                 import_files: Vec::new(),
             })
         } else {
@@ -32,6 +38,7 @@ mod include_filenames_tests {
                 claude: claude_config,
                 ..AgentConfig::default()
             },
+            ..Default::default()
         }
     }
 
@@ -285,4 +292,66 @@ mod include_filenames_tests {
         // 空のディレクトリの場合は空文字列
         assert!(result.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_merge_all_with_heading_mode() {
+        use crate::types::HeadingMode;
+
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+        fs::write(
+            docs_path.join("guide.md"),
+            "# Guide\n\n## Setup\n\n```sh\n# comment\n```",
+        )
+        .await
+        .unwrap();
+
+        let mut config = create_test_config_with_include_filenames(
+            &docs_path.to_string_lossy(),
+            Some(true),
+            None,
+        );
+        config.heading_mode = Some(HeadingMode::Demote);
+        let result = MarkdownMerger::new(config.clone())
+            .merge_all_with_options(Some("claude"))
+            .await
+            .unwrap();
+        // ドキュメントの見出しがファイル名ヘッダーの下に1段下がる（コードブロック内は対象外）
+        assert_eq!(
+            result,
+            "# guide.md\n\n## Guide\n\n### Setup\n\n```sh\n# comment\n```"
+        );
+
+        config.heading_mode = Some(HeadingMode::DocTitle);
+        let result = MarkdownMerger::new(config)
+            .merge_all_with_options(Some("claude"))
+            .await
+            .unwrap();
+        // ドキュメントのH1がセクションタイトルになる
+        assert_eq!(result, "# Guide\n\n## Setup\n\n```sh\n# comment\n```");
+    }
+
+    #[tokio::test]
+    async fn test_merge_all_with_toc() {
+        let temp_dir = tempdir().unwrap();
+        let docs_path = temp_dir.path();
+        fs::write(docs_path.join("a.md"), "# Guide\n\n## Setup")
+            .await
+            .unwrap();
+        fs::write(docs_path.join("b.md"), "# Testing\n\n## Setup")
+            .await
+            .unwrap();
+
+        let mut config =
+            create_test_config_with_include_filenames(&docs_path.to_string_lossy(), None, None);
+        config.toc = Some(true);
+        let merger = MarkdownMerger::new(config);
+
+        let result = merger.merge_all_with_options(Some("claude")).await.unwrap();
+
+        // 目次が先頭に追加され、重複する見出しには連番付きアンカーが使われる
+        assert!(result.starts_with(
+            "**Table of Contents**\n\n- [Guide](#guide)\n  - [Setup](#setup)\n- [Testing](#testing)\n  - [Setup](#setup-1)\n\n# Guide"
+        ));
+    }
 }
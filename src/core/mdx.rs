@@ -0,0 +1,61 @@
+/*!
+ * AI Context Management Tool - MDX Support
+ *
+ * Strips MDX import/export statements so .mdx docs read as plain Markdown
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+
+/// Remove top-level `import`/`export` statements from MDX content
+/// A statement runs until the next blank line, as in MDX; code blocks are left untouched
+pub fn strip_mdx_esm(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut fence = CodeFenceTracker::new();
+    let mut in_statement = false;
+    let mut stripped = false;
+
+    for line in content.split_inclusive('\n') {
+        if in_statement {
+            in_statement = !line.trim().is_empty();
+            continue;
+        }
+        if !fence.update(line) && is_esm_line(line) {
+            in_statement = true;
+            stripped = true;
+            continue;
+        }
+        // Drop blank lines left at the top after removed statements
+        if stripped && output.is_empty() && line.trim().is_empty() {
+            continue;
+        }
+        output.push_str(line);
+    }
+
+    output
+}
+
+fn is_esm_line(line: &str) -> bool {
+    ["import ", "import{", "export "]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_mdx_esm() {
+        let content = "import { Tabs } from './tabs'\nimport Note from './note'\n\nexport const meta = {\n  title: 'Guide',\n}\n\n# Guide\n\nImport the module:\n\n```js\nimport x from 'x'\n```\n";
+        assert_eq!(
+            strip_mdx_esm(content),
+            "# Guide\n\nImport the module:\n\n```js\nimport x from 'x'\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_strip_mdx_esm_without_statements() {
+        let content = "\n# Plain\n\nimported text\n";
+        assert_eq!(strip_mdx_esm(content), content);
+    }
+}
@@ -4,9 +4,45 @@
  * This module provides core functions.
  */
 
+pub mod code_fence;
+pub mod conditional;
+pub mod doc_filter;
+pub mod encoding;
+pub mod frontmatter;
+pub mod headings;
+pub mod include;
+pub mod links;
+pub mod lint;
 pub mod markdown_merger;
+pub mod mdx;
+pub mod packs;
+pub mod rule_coverage;
+pub mod secrets;
+pub mod sections;
+pub mod split_rules;
+pub mod template;
+pub mod toc;
+pub mod tokens;
 
 #[cfg(test)]
 mod markdown_merger_test;
 
+pub use code_fence::*;
+pub use conditional::*;
+pub use doc_filter::*;
+pub use encoding::*;
+pub use frontmatter::*;
+pub use headings::*;
+pub use include::*;
+pub use links::*;
+pub use lint::*;
 pub use markdown_merger::*;
+pub use mdx::*;
+pub use packs::*;
+pub use rule_coverage::*;
+pub use secrets::*;
+pub use sections::*;
+pub use split_rules::*;
+pub use template::*;
+pub use toc::*;
+pub use tokens::*;
@@ -0,0 +1,491 @@
+/*!
+ * AI Context Management Tool - Doc Packs
+ *
+ * Vendors shared doc packs from local git repositories or archives into a cache directory
+ */
+
+use crate::types::PackConfig;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Cache directory packs are vendored into (relative to the project root)
+pub const PACKS_DIR: &str = ".aicm/packs";
+
+/// Lock file recording the vendored source and pinned commit of each pack
+pub const PACKS_LOCK_FILE: &str = ".aicm/packs.lock";
+
+/// Vendored state of a single pack
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackLockEntry {
+    /// Configured git repository or archive
+    pub source: String,
+    /// Configured git ref
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Commit the ref resolved to when the pack was vendored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// Contents of the packs lock file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PackLock {
+    #[serde(default)]
+    pub packs: BTreeMap<String, PackLockEntry>,
+}
+
+/// Vendors packs under a project root
+pub struct PackManager {
+    root: PathBuf,
+}
+
+impl PackManager {
+    /// Create a manager for the project at `root` (relative pack sources are resolved from it)
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// Cache directory of a pack
+    pub fn pack_dir(&self, pack: &PackConfig) -> PathBuf {
+        self.root.join(PACKS_DIR).join(&pack.name)
+    }
+
+    /// Docs directory of a vendored pack
+    /// `path` must stay inside the pack (no absolute paths or `..` components)
+    pub fn docs_dir(&self, pack: &PackConfig) -> Result<PathBuf> {
+        match &pack.path {
+            Some(path) => {
+                validate_pack_path(pack, path)?;
+                Ok(self.pack_dir(pack).join(path))
+            }
+            None => Ok(self.pack_dir(pack)),
+        }
+    }
+
+    /// Read the lock file (empty if it doesn't exist)
+    pub fn read_lock(&self) -> Result<PackLock> {
+        let path = self.root.join(PACKS_LOCK_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_yaml::from_str(&content)
+                .with_context(|| format!("Failed to parse {PACKS_LOCK_FILE}")),
+            Err(_) => Ok(PackLock::default()),
+        }
+    }
+
+    fn write_lock(&self, lock: &PackLock) -> Result<()> {
+        let path = self.root.join(PACKS_LOCK_FILE);
+        let content = serde_yaml::to_string(lock)?;
+        fs::write(
+            &path,
+            format!("# Generated by aicm. Run `aicm packs update` to refresh.\n{content}"),
+        )
+        .with_context(|| format!("Failed to write {PACKS_LOCK_FILE}"))
+    }
+
+    /// Vendor packs that are missing from the cache or whose source changed
+    /// Git packs already in the lock file are vendored at their pinned commit
+    /// Returns the names of vendored packs
+    pub fn sync(&self, packs: &[PackConfig]) -> Result<Vec<String>> {
+        self.vendor_packs(packs, |_| false)
+    }
+
+    /// Re-resolve the refs of all packs (or only `name`) and vendor them again
+    /// Returns the names of vendored packs
+    pub fn update(&self, packs: &[PackConfig], name: Option<&str>) -> Result<Vec<String>> {
+        if let Some(name) = name {
+            if !packs.iter().any(|pack| pack.name == name) {
+                bail!("Pack '{name}' is not configured");
+            }
+        }
+        self.vendor_packs(packs, |pack| name.is_none_or(|name| pack.name == name))
+    }
+
+    fn vendor_packs<F>(&self, packs: &[PackConfig], refresh: F) -> Result<Vec<String>>
+    where
+        F: Fn(&PackConfig) -> bool,
+    {
+        let mut lock = self.read_lock()?;
+        let mut vendored = Vec::new();
+
+        for pack in packs {
+            validate_pack(pack)?;
+            let source = pack_source(pack).to_string();
+            let pinned = lock.packs.get(&pack.name).filter(|entry| {
+                entry.source == source && entry.git_ref == pack.git_ref && !refresh(pack)
+            });
+            if pinned.is_some() && self.pack_dir(pack).is_dir() {
+                continue;
+            }
+
+            let commit = match &pack.git {
+                Some(_) => Some(match pinned.and_then(|entry| entry.commit.clone()) {
+                    Some(commit) => commit,
+                    None => self.resolve_commit(pack)?,
+                }),
+                None => None,
+            };
+            self.vendor(pack, commit.as_deref())
+                .with_context(|| format!("Failed to vendor pack '{}'", pack.name))?;
+
+            lock.packs.insert(
+                pack.name.clone(),
+                PackLockEntry {
+                    source,
+                    git_ref: pack.git_ref.clone(),
+                    commit,
+                },
+            );
+            vendored.push(pack.name.clone());
+        }
+
+        let before = lock.packs.len();
+        lock.packs
+            .retain(|name, _| packs.iter().any(|pack| &pack.name == name));
+        if !vendored.is_empty() || lock.packs.len() != before {
+            self.write_lock(&lock)?;
+        }
+        Ok(vendored)
+    }
+
+    /// Resolve the pack's ref to a commit id
+    fn resolve_commit(&self, pack: &PackConfig) -> Result<String> {
+        let repo = self.git_repo_path(pack)?;
+        let git_ref = pack.git_ref.as_deref().unwrap_or("HEAD");
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("{git_ref}^{{commit}}"))
+            .output()
+            .map_err(|e| tool_error("git", e))?;
+        if !output.status.success() {
+            bail!(
+                "Pack '{}': ref '{git_ref}' not found in {}",
+                pack.name,
+                repo.display()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Extract the pack into a temporary directory, then replace the cache directory
+    fn vendor(&self, pack: &PackConfig, commit: Option<&str>) -> Result<()> {
+        let packs_dir = self.root.join(PACKS_DIR);
+        let staging = packs_dir.join(format!(".{}.tmp", pack.name));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+
+        let result = match (commit, &pack.archive) {
+            (Some(commit), _) => self.extract_git(pack, commit, &staging),
+            (None, Some(archive)) => extract_archive(&self.root.join(archive), &staging),
+            (None, None) => bail!("Pack '{}' has no source", pack.name),
+        };
+        if let Err(e) = result {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+
+        let pack_dir = self.pack_dir(pack);
+        if pack_dir.exists() {
+            fs::remove_dir_all(&pack_dir)?;
+        }
+        fs::rename(&staging, &pack_dir)?;
+        Ok(())
+    }
+
+    fn extract_git(&self, pack: &PackConfig, commit: &str, dest: &Path) -> Result<()> {
+        let repo = self.git_repo_path(pack)?;
+        let archive = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["archive", "--format=tar", commit])
+            .output()
+            .map_err(|e| tool_error("git", e))?;
+        if !archive.status.success() {
+            bail!(
+                "git archive failed: {}",
+                String::from_utf8_lossy(&archive.stderr).trim()
+            );
+        }
+
+        let mut tar = Command::new("tar")
+            .arg("-xf")
+            .arg("-")
+            .arg("-C")
+            .arg(dest)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| tool_error("tar", e))?;
+        if let Some(mut stdin) = tar.stdin.take() {
+            stdin.write_all(&archive.stdout)?;
+        }
+        if !tar.wait()?.success() {
+            bail!("tar failed to extract git archive");
+        }
+        Ok(())
+    }
+
+    /// Local path of the pack's git repository (plain path or file:// URL)
+    fn git_repo_path(&self, pack: &PackConfig) -> Result<PathBuf> {
+        let git = pack.git.as_deref().unwrap_or_default();
+        if let Some(path) = git.strip_prefix("file://") {
+            return Ok(PathBuf::from(path));
+        }
+        if git.contains("://") || git.contains('@') {
+            bail!(
+                "Pack '{}': only local paths and file:// URLs are supported as git sources",
+                pack.name
+            );
+        }
+        Ok(self.root.join(git))
+    }
+}
+
+/// Check the pack name and that exactly one source is configured
+fn validate_pack(pack: &PackConfig) -> Result<()> {
+    let name = &pack.name;
+    if name.is_empty()
+        || name.starts_with('.')
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!("Invalid pack name '{name}': use letters, digits, '-', '_' or '.'");
+    }
+    if let Some(path) = &pack.path {
+        validate_pack_path(pack, path)?;
+    }
+    match (&pack.git, &pack.archive) {
+        (Some(_), None) | (None, Some(_)) => Ok(()),
+        _ => bail!("Pack '{name}' must specify exactly one of git or archive"),
+    }
+}
+
+/// Check that a pack's docs path is relative and stays inside the pack
+fn validate_pack_path(pack: &PackConfig, path: &str) -> Result<()> {
+    let inside = Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !inside {
+        bail!(
+            "Pack '{}': path '{path}' must be a relative path inside the pack",
+            pack.name
+        );
+    }
+    Ok(())
+}
+
+fn pack_source(pack: &PackConfig) -> &str {
+    pack.git
+        .as_deref()
+        .or(pack.archive.as_deref())
+        .unwrap_or_default()
+}
+
+/// Error for an external command that could not be run
+/// A missing command gets an explicit hint instead of the bare OS error
+fn tool_error(tool: &str, error: io::Error) -> anyhow::Error {
+    if error.kind() == io::ErrorKind::NotFound {
+        anyhow::anyhow!("`{tool}` is required to vendor packs but was not found on PATH")
+    } else {
+        anyhow::Error::new(error).context(format!("Failed to run {tool}"))
+    }
+}
+
+/// Extract a .zip (with unzip) or tar archive (with tar, compression auto-detected)
+fn extract_archive(archive: &Path, dest: &Path) -> Result<()> {
+    if !archive.is_file() {
+        bail!("archive not found: {}", archive.display());
+    }
+    let is_zip = archive
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+
+    let status = if is_zip {
+        Command::new("unzip")
+            .arg("-q")
+            .arg(archive)
+            .arg("-d")
+            .arg(dest)
+            .status()
+            .map_err(|e| tool_error("unzip", e))?
+    } else {
+        Command::new("tar")
+            .arg("-xf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dest)
+            .status()
+            .map_err(|e| tool_error("tar", e))?
+    };
+    if !status.success() {
+        bail!("Failed to extract {}", archive.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn commit_doc(repo: &Path, content: &str) -> String {
+        fs::create_dir_all(repo.join("docs")).unwrap();
+        fs::write(repo.join("docs/guidelines.md"), content).unwrap();
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-q", "-m", content]);
+        git(repo, &["rev-parse", "HEAD"])
+    }
+
+    fn git_pack(git: &str) -> PackConfig {
+        PackConfig {
+            name: "company".to_string(),
+            git: Some(git.to_string()),
+            git_ref: Some("main".to_string()),
+            archive: None,
+            path: Some("docs".to_string()),
+            prefix: None,
+        }
+    }
+
+    #[test]
+    fn test_sync_pins_commit_and_update_refreshes() {
+        let temp_dir = tempdir().unwrap();
+        let repo = temp_dir.path().join("guidelines");
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(&repo).unwrap();
+        fs::create_dir_all(&project).unwrap();
+        git(&repo, &["init", "-q", "-b", "main"]);
+        let first = commit_doc(&repo, "v1");
+
+        let manager = PackManager::new(&project);
+        let packs = vec![git_pack(&format!("file://{}", repo.display()))];
+        assert_eq!(manager.sync(&packs).unwrap(), vec!["company"]);
+        let guideline = manager.docs_dir(&packs[0]).unwrap().join("guidelines.md");
+        assert_eq!(fs::read_to_string(&guideline).unwrap(), "v1");
+        assert_eq!(
+            manager.read_lock().unwrap().packs["company"].commit,
+            Some(first.clone())
+        );
+
+        // New upstream commits are not picked up until update
+        let second = commit_doc(&repo, "v2");
+        assert!(manager.sync(&packs).unwrap().is_empty());
+        fs::remove_dir_all(manager.pack_dir(&packs[0])).unwrap();
+        manager.sync(&packs).unwrap();
+        assert_eq!(fs::read_to_string(&guideline).unwrap(), "v1");
+
+        manager.update(&packs, None).unwrap();
+        assert_eq!(fs::read_to_string(&guideline).unwrap(), "v2");
+        assert_eq!(
+            manager.read_lock().unwrap().packs["company"].commit,
+            Some(second)
+        );
+
+        // Changing the configured ref vendors the pack again on the next sync
+        git(&repo, &["tag", "v1", &first]);
+        let mut pinned = packs[0].clone();
+        pinned.git_ref = Some("v1".to_string());
+        assert_eq!(manager.sync(&[pinned]).unwrap(), vec!["company"]);
+        assert_eq!(fs::read_to_string(&guideline).unwrap(), "v1");
+    }
+
+    #[test]
+    fn test_sync_archive_pack() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/docs")).unwrap();
+        fs::write(root.join("src/docs/security.md"), "No secrets").unwrap();
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(root.join("security.tar.gz"))
+            .arg("-C")
+            .arg(root.join("src"))
+            .arg("docs")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let pack = PackConfig {
+            name: "security".to_string(),
+            git: None,
+            git_ref: None,
+            archive: Some("security.tar.gz".to_string()),
+            path: Some("docs".to_string()),
+            prefix: None,
+        };
+        let manager = PackManager::new(root);
+        manager.sync(std::slice::from_ref(&pack)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(manager.docs_dir(&pack).unwrap().join("security.md")).unwrap(),
+            "No secrets"
+        );
+        assert_eq!(manager.read_lock().unwrap().packs["security"].commit, None);
+    }
+
+    #[test]
+    fn test_invalid_pack_config() {
+        let temp_dir = tempdir().unwrap();
+        let manager = PackManager::new(temp_dir.path());
+
+        let mut pack = git_pack("../repo");
+        pack.archive = Some("docs.zip".to_string());
+        let error = manager.sync(&[pack]).unwrap_err();
+        assert!(error.to_string().contains("exactly one of git or archive"));
+
+        let mut pack = git_pack("../repo");
+        pack.name = "../escape".to_string();
+        assert!(manager.sync(&[pack]).is_err());
+
+        for path in ["../..", "/etc", "docs/../../outside"] {
+            let mut pack = git_pack("../repo");
+            pack.path = Some(path.to_string());
+            let error = manager.sync(&[pack.clone()]).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("must be a relative path inside the pack"));
+            assert!(manager.docs_dir(&pack).is_err());
+        }
+
+        let pack = git_pack("https://example.com/repo.git");
+        let error = manager.sync(&[pack]).unwrap_err();
+        assert!(format!("{error:#}").contains("only local paths"));
+
+        let error = manager.update(&[], Some("missing")).unwrap_err();
+        assert!(error.to_string().contains("not configured"));
+    }
+
+    #[test]
+    fn test_missing_tool_error() {
+        let error = Command::new("aicm-missing-tool")
+            .output()
+            .map_err(|e| tool_error("aicm-missing-tool", e))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`aicm-missing-tool` is required to vendor packs but was not found on PATH"
+        );
+    }
+}
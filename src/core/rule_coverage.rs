@@ -0,0 +1,288 @@
+/*!
+ * AI Context Management Tool - Split Rule Coverage
+ *
+ * Records which split rule each source document matched for an agent
+ */
+
+use anyhow::Result;
+
+/// Match result of a single split rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    /// Rule position in split_config.rules (1-based, as shown to users)
+    pub number: usize,
+    /// Target Markdown filename patterns of the rule
+    pub file_patterns: Vec<String>,
+    /// Docs that were assigned to this rule
+    pub matched_docs: Vec<String>,
+    /// Docs that matched this rule but were already taken by an earlier rule
+    pub shadowed_docs: Vec<String>,
+}
+
+/// Coverage of split rules over the source docs of one agent
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleCoverage {
+    /// Agent name
+    pub agent: String,
+    /// Match result per rule, in configuration order
+    pub rules: Vec<RuleMatch>,
+    /// Docs that matched no rule but declare rule metadata in their own frontmatter
+    pub frontmatter_docs: Vec<String>,
+    /// Docs that matched no rule and got the default frontmatter
+    pub fallback_docs: Vec<String>,
+}
+
+impl RuleCoverage {
+    /// Build coverage with first-match-wins semantics
+    /// `matcher` must be the same pattern check the agent uses when generating
+    pub fn build<F>(
+        agent: &str,
+        docs: &[String],
+        rule_patterns: &[&[String]],
+        matcher: F,
+    ) -> Result<Self>
+    where
+        F: Fn(&str, &[String]) -> Result<bool>,
+    {
+        let mut rules: Vec<RuleMatch> = rule_patterns
+            .iter()
+            .enumerate()
+            .map(|(index, patterns)| RuleMatch {
+                number: index + 1,
+                file_patterns: patterns.to_vec(),
+                matched_docs: Vec::new(),
+                shadowed_docs: Vec::new(),
+            })
+            .collect();
+        let mut fallback_docs = Vec::new();
+
+        for doc in docs {
+            let mut assigned = false;
+            for rule in rules.iter_mut() {
+                if !matcher(doc, &rule.file_patterns)? {
+                    continue;
+                }
+                if assigned {
+                    rule.shadowed_docs.push(doc.clone());
+                } else {
+                    rule.matched_docs.push(doc.clone());
+                    assigned = true;
+                }
+            }
+            if !assigned {
+                fallback_docs.push(doc.clone());
+            }
+        }
+
+        Ok(Self {
+            agent: agent.to_string(),
+            rules,
+            frontmatter_docs: Vec::new(),
+            fallback_docs,
+        })
+    }
+
+    /// Move unmatched docs whose own frontmatter provides rule metadata out of the fallback list
+    pub fn mark_frontmatter_docs(&mut self, docs: &[String]) {
+        let (frontmatter_docs, fallback_docs) = self
+            .fallback_docs
+            .drain(..)
+            .partition(|doc| docs.contains(doc));
+        self.frontmatter_docs = frontmatter_docs;
+        self.fallback_docs = fallback_docs;
+    }
+
+    /// Get the rule a doc was assigned to
+    pub fn rule_for(&self, doc: &str) -> Option<&RuleMatch> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matched_docs.iter().any(|d| d == doc))
+    }
+
+    /// What happens to a doc that matches no rule for this agent
+    pub fn fallback_description(&self) -> &'static str {
+        match self.agent.as_str() {
+            // Instruction files without applyTo are general instructions
+            "github" => "written as general instructions without applyTo",
+            // Rules without a mode go to the shared .roo/rules/
+            "roo" => "written to the shared .roo/rules/",
+            _ => "default frontmatter used",
+        }
+    }
+
+    /// Render doc-to-rule matrix (one line per doc)
+    pub fn render_matrix(&self) -> String {
+        let mut docs: Vec<&String> = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.matched_docs.iter())
+            .chain(self.frontmatter_docs.iter())
+            .chain(self.fallback_docs.iter())
+            .collect();
+        docs.sort();
+
+        let width = docs.iter().map(|d| d.len()).max().unwrap_or(0);
+        let mut lines = Vec::new();
+        for doc in docs {
+            let target = match self.rule_for(doc) {
+                Some(rule) => format!("rule #{} ({})", rule.number, rule.file_patterns.join(", ")),
+                None if self.frontmatter_docs.contains(doc) => "doc frontmatter".to_string(),
+                None => format!("fallback ({})", self.fallback_description()),
+            };
+            lines.push(format!("{doc:<width$}  {target}"));
+        }
+        lines.join("\n")
+    }
+
+    /// Warnings for rules that match nothing and docs that only got the fallback
+    pub fn warnings(&self) -> Vec<String> {
+        let agent = &self.agent;
+        let mut warnings = Vec::new();
+
+        for rule in &self.rules {
+            if !rule.matched_docs.is_empty() {
+                continue;
+            }
+            let patterns = rule.file_patterns.join(", ");
+            if rule.shadowed_docs.is_empty() {
+                warnings.push(format!(
+                    "{agent}: rule #{} ({patterns}) matches no docs",
+                    rule.number
+                ));
+            } else {
+                warnings.push(format!(
+                    "{agent}: rule #{} ({patterns}) is shadowed by earlier rules for {}",
+                    rule.number,
+                    rule.shadowed_docs.join(", ")
+                ));
+            }
+        }
+
+        for doc in &self.fallback_docs {
+            warnings.push(format!(
+                "{agent}: {doc} matches no rule ({})",
+                self.fallback_description()
+            ));
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains_matcher(doc: &str, patterns: &[String]) -> Result<bool> {
+        Ok(patterns.iter().any(|p| doc.contains(p.as_str())))
+    }
+
+    fn docs(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_build_first_match_wins() {
+        let rule1 = vec!["project".to_string()];
+        let rule2 = vec!["rust".to_string(), "project".to_string()];
+        let coverage = RuleCoverage::build(
+            "cursor",
+            &docs(&["project.md", "rust.md", "notes.md"]),
+            &[&rule1, &rule2],
+            contains_matcher,
+        )
+        .unwrap();
+
+        assert_eq!(coverage.rules[0].matched_docs, docs(&["project.md"]));
+        assert_eq!(coverage.rules[1].matched_docs, docs(&["rust.md"]));
+        assert_eq!(coverage.rules[1].shadowed_docs, docs(&["project.md"]));
+        assert_eq!(coverage.fallback_docs, docs(&["notes.md"]));
+        assert_eq!(coverage.rule_for("rust.md").unwrap().number, 2);
+        assert!(coverage.rule_for("notes.md").is_none());
+    }
+
+    #[test]
+    fn test_warnings_for_unused_rules_and_fallback_docs() {
+        let rule1 = vec!["project".to_string()];
+        let rule2 = vec!["project-overview".to_string()];
+        let rule3 = vec!["missing".to_string()];
+        let coverage = RuleCoverage::build(
+            "kiro",
+            &docs(&["project-overview.md", "notes.md"]),
+            &[&rule1, &rule2, &rule3],
+            contains_matcher,
+        )
+        .unwrap();
+
+        let warnings = coverage.warnings();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("rule #2"));
+        assert!(warnings[0].contains("shadowed"));
+        assert!(warnings[1].contains("rule #3 (missing) matches no docs"));
+        assert_eq!(
+            warnings[2],
+            "kiro: notes.md matches no rule (default frontmatter used)"
+        );
+    }
+
+    #[test]
+    fn test_fallback_description_depends_on_agent() {
+        let rule = vec!["project".to_string()];
+        let coverage =
+            RuleCoverage::build("github", &docs(&["a.md"]), &[&rule], contains_matcher).unwrap();
+
+        assert_eq!(
+            coverage.warnings()[1],
+            "github: a.md matches no rule (written as general instructions without applyTo)"
+        );
+        assert!(coverage
+            .render_matrix()
+            .contains("fallback (written as general instructions without applyTo)"));
+    }
+
+    #[test]
+    fn test_render_matrix() {
+        let rule = vec!["project".to_string()];
+        let coverage = RuleCoverage::build(
+            "github",
+            &docs(&["project.md", "a.md"]),
+            &[&rule],
+            contains_matcher,
+        )
+        .unwrap();
+
+        let matrix = coverage.render_matrix();
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("a.md"));
+        assert!(lines[0].contains("fallback"));
+        assert!(lines[1].contains("rule #1 (project)"));
+    }
+
+    #[test]
+    fn test_mark_frontmatter_docs() {
+        let rule = vec!["project".to_string()];
+        let mut coverage = RuleCoverage::build(
+            "cursor",
+            &docs(&["project.md", "rust.md", "notes.md"]),
+            &[&rule],
+            contains_matcher,
+        )
+        .unwrap();
+        coverage.mark_frontmatter_docs(&docs(&["rust.md"]));
+
+        assert_eq!(coverage.frontmatter_docs, docs(&["rust.md"]));
+        assert_eq!(coverage.fallback_docs, docs(&["notes.md"]));
+        assert_eq!(coverage.warnings().len(), 1);
+        assert!(coverage.render_matrix().contains("doc frontmatter"));
+    }
+
+    #[test]
+    fn test_matcher_error_is_propagated() {
+        let rule = vec!["[".to_string()];
+        let result = RuleCoverage::build("kiro", &docs(&["a.md"]), &[&rule], |_, _| {
+            Err(anyhow::anyhow!("invalid pattern"))
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,349 @@
+/*!
+ * AI Context Management Tool - Doc Sections
+ *
+ * Parses docs into a tree of heading sections to split, select or drop sections
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use crate::core::headings::heading_level;
+use crate::core::toc::{heading_text, SlugCounter};
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+
+/// Part of a doc starting at a heading
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// Heading text (None for content before the first section heading)
+    pub heading: Option<String>,
+    /// Unique GitHub-style slug of the heading, used in section file names
+    pub slug: String,
+    /// Section content including its heading line
+    pub content: String,
+}
+
+/// Split content at every heading of `level`; deeper headings stay inside their section
+/// Content before the first section heading is returned first unless it only holds headings
+/// Returns a single section without heading when the content has no heading of `level`
+pub fn split_sections(content: &str, level: usize) -> Vec<Section> {
+    let mut slugs = SlugCounter::default();
+    let mut sections = vec![Section {
+        heading: None,
+        slug: String::new(),
+        content: String::new(),
+    }];
+
+    for (line, heading_level) in block_lines(content) {
+        if heading_level == Some(level) {
+            let heading = heading_text(line);
+            let mut slug = slugs.unique(&heading);
+            if slug.is_empty() {
+                slug = format!("section-{}", sections.len());
+            }
+            sections.push(Section {
+                heading: Some(heading),
+                slug,
+                content: String::new(),
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            section.content.push_str(line);
+        }
+    }
+
+    if sections.len() > 1 {
+        let preamble = &sections[0].content;
+        let has_body = block_lines(preamble)
+            .any(|(line, heading_level)| heading_level.is_none() && !line.trim().is_empty());
+        if !has_body {
+            sections.remove(0);
+        }
+    }
+    for section in &mut sections {
+        section.content = section.content.trim_end().to_string();
+    }
+    sections
+}
+
+/// Node of a doc's section tree: a heading, the lines up to its first subsection, and its subsections
+/// Rendering the tree reproduces the doc byte for byte, so code blocks, tables and HTML blocks are untouched
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SectionNode {
+    /// Heading level and text (None for the doc root)
+    pub heading: Option<(usize, String)>,
+    /// Heading line and the content before the first subsection
+    pub content: String,
+    /// Nested sections in doc order
+    pub children: Vec<SectionNode>,
+}
+
+impl SectionNode {
+    /// Parse content into a section tree rooted at a heading-less node
+    pub fn parse(content: &str) -> Self {
+        let mut stack = vec![SectionNode::default()];
+
+        for (line, level) in block_lines(content) {
+            let Some(level) = level else {
+                if let Some(node) = stack.last_mut() {
+                    node.content.push_str(line);
+                }
+                continue;
+            };
+            // Close sections at the same or a deeper level
+            while stack.len() > 1 && stack.last().is_some_and(|node| node.level() >= level) {
+                close_section(&mut stack);
+            }
+            stack.push(SectionNode {
+                heading: Some((level, heading_text(line))),
+                content: line.to_string(),
+                children: Vec::new(),
+            });
+        }
+
+        while stack.len() > 1 {
+            close_section(&mut stack);
+        }
+        stack.pop().unwrap_or_default()
+    }
+
+    /// Render the tree back to Markdown
+    pub fn render(&self) -> String {
+        let mut output = self.content.clone();
+        for child in &self.children {
+            output.push_str(&child.render());
+        }
+        output
+    }
+
+    fn level(&self) -> usize {
+        self.heading.as_ref().map_or(0, |(level, _)| *level)
+    }
+}
+
+fn close_section(stack: &mut Vec<SectionNode>) {
+    if let (Some(node), Some(parent)) = (stack.pop(), stack.last_mut()) {
+        parent.children.push(node);
+    }
+}
+
+/// Matches sections by heading text (case-insensitive, `*` and `?` wildcards allowed)
+#[derive(Debug, Clone, Default)]
+pub struct SectionSelector {
+    patterns: Vec<Pattern>,
+}
+
+impl SectionSelector {
+    /// Build a selector from heading texts or globs
+    pub fn new(selectors: &[String]) -> Result<Self> {
+        let patterns = selectors
+            .iter()
+            .map(|selector| {
+                Pattern::new(selector.trim())
+                    .with_context(|| format!("Invalid section selector: {selector}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Check whether the selector has no patterns
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Check whether a section's heading matches the selector
+    pub fn matches(&self, node: &SectionNode) -> bool {
+        let options = MatchOptions {
+            case_sensitive: false,
+            ..MatchOptions::new()
+        };
+        node.heading.as_ref().is_some_and(|(_, text)| {
+            self.patterns
+                .iter()
+                .any(|pattern| pattern.matches_with(text, options))
+        })
+    }
+
+    /// Remove matching sections (with their subsections) from content
+    pub fn drop_from(&self, content: &str) -> String {
+        if self.is_empty() {
+            return content.to_string();
+        }
+        let mut tree = SectionNode::parse(content);
+        self.drop_matching(&mut tree);
+        tree.render()
+    }
+
+    /// Keep only matching sections (with their subsections), in doc order
+    pub fn select_from(&self, content: &str) -> String {
+        let tree = SectionNode::parse(content);
+        let mut selected = Vec::new();
+        self.collect_matching(&tree, &mut selected);
+        selected
+            .iter()
+            .map(|node| node.render().trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn drop_matching(&self, node: &mut SectionNode) {
+        node.children.retain(|child| !self.matches(child));
+        for child in &mut node.children {
+            self.drop_matching(child);
+        }
+    }
+
+    fn collect_matching<'a>(&self, node: &'a SectionNode, selected: &mut Vec<&'a SectionNode>) {
+        if self.matches(node) {
+            selected.push(node);
+            return;
+        }
+        for child in &node.children {
+            self.collect_matching(child, selected);
+        }
+    }
+}
+
+/// Iterate lines (with line endings) and their ATX heading level outside code and HTML blocks
+/// An HTML block starts at a line beginning with a tag or comment and ends at the next blank line
+fn block_lines(content: &str) -> impl Iterator<Item = (&str, Option<usize>)> {
+    let mut fence = CodeFenceTracker::new();
+    let mut in_html = false;
+    content.split_inclusive('\n').map(move |line| {
+        if fence.update(line) {
+            return (line, None);
+        }
+        if in_html {
+            in_html = !line.trim().is_empty();
+            return (line, None);
+        }
+        if starts_html_block(line) {
+            in_html = true;
+            return (line, None);
+        }
+        (line, heading_level(line))
+    })
+}
+
+/// Check whether a line opens an HTML block: a comment or declaration, or a tag name followed by
+/// whitespace, `>` or `/>` (autolinks such as `<https://example.com>` are not tags)
+fn starts_html_block(line: &str) -> bool {
+    let Some(rest) = line.trim().strip_prefix('<') else {
+        return false;
+    };
+    if rest.starts_with('!') {
+        return true;
+    }
+
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return false;
+    }
+    let after_name = rest.trim_start_matches(|c: char| c.is_ascii_alphanumeric() || c == '-');
+    after_name.is_empty()
+        || after_name.starts_with(char::is_whitespace)
+        || after_name.starts_with('>')
+        || after_name.starts_with("/>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sections_at_h2() {
+        let content = "# Design\n\n## Auth\n\nTokens.\n\n### Refresh\n\n```md\n## Not a heading\n```\n\n## Storage\n\nBuckets.\n\n## Auth\n\nAgain.\n";
+        let sections = split_sections(content, 2);
+
+        let slugs: Vec<&str> = sections.iter().map(|s| s.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["auth", "storage", "auth-1"]);
+        assert_eq!(
+            sections[0].content,
+            "## Auth\n\nTokens.\n\n### Refresh\n\n```md\n## Not a heading\n```"
+        );
+        assert_eq!(sections[1].heading.as_deref(), Some("Storage"));
+        assert_eq!(sections[2].content, "## Auth\n\nAgain.");
+    }
+
+    #[test]
+    fn test_section_tree_round_trip() {
+        let content = "Intro\n# A\n\ntext\n\n## B\n\n| x |\n|---|\n\n### C\n\n## D\n<div>\n# not a heading\n</div>\n\n# E\n";
+        let tree = SectionNode::parse(content);
+        assert_eq!(tree.render(), content);
+        assert_eq!(tree.content, "Intro\n");
+
+        let headings: Vec<(usize, &str, usize)> = tree.children[0]
+            .children
+            .iter()
+            .map(|node| {
+                let (level, text) = node.heading.as_ref().unwrap();
+                (*level, text.as_str(), node.children.len())
+            })
+            .collect();
+        assert_eq!(headings, vec![(2, "B", 1), (2, "D", 0)]);
+        assert_eq!(tree.children[1].heading, Some((1, "E".to_string())));
+    }
+
+    #[test]
+    fn test_starts_html_block() {
+        for line in [
+            "<div>",
+            "<div class=\"note\">",
+            "</details>",
+            "<br/>",
+            "<img",
+            "<!-- x -->",
+        ] {
+            assert!(starts_html_block(line), "{line}");
+        }
+        for line in [
+            "<https://example.com>",
+            "<mailto:team@example.com>",
+            "<3 tests",
+            "< div>",
+        ] {
+            assert!(!starts_html_block(line), "{line}");
+        }
+
+        // A heading right after an autolink line still starts a section
+        let tree = SectionNode::parse(
+            "# A
+<https://example.com>
+## B
+",
+        );
+        assert_eq!(
+            tree.children[0].children[0].heading,
+            Some((2, "B".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_section_selector() {
+        let content = "# Guide\n\n## API Reference\n\nCalls.\n\n### Errors\n\nCodes.\n\n## Onboarding\n\nHello.\n\n## API Changes\n\nNew.\n";
+        let selector = SectionSelector::new(&["api*".to_string()]).unwrap();
+        assert_eq!(
+            selector.select_from(content),
+            "## API Reference\n\nCalls.\n\n### Errors\n\nCodes.\n\n## API Changes\n\nNew."
+        );
+
+        let selector =
+            SectionSelector::new(&["Onboarding".to_string(), "Errors".to_string()]).unwrap();
+        assert_eq!(
+            selector.drop_from(content),
+            "# Guide\n\n## API Reference\n\nCalls.\n\n## API Changes\n\nNew.\n"
+        );
+        assert_eq!(SectionSelector::default().drop_from(content), content);
+        assert!(SectionSelector::new(&["[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_split_sections_keeps_preamble_with_body() {
+        let sections = split_sections("# Design\n\nOverview.\n\n## Auth\n\nTokens.\n", 2);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].heading, None);
+        assert_eq!(sections[0].content, "# Design\n\nOverview.");
+
+        let sections = split_sections("# Design\n\nNo sections.\n", 2);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading, None);
+    }
+}
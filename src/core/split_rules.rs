@@ -0,0 +1,162 @@
+/*!
+ * AI Context Management Tool - Split Rules
+ *
+ * Matches docs against split_config rules and resolves Cursor-style rules with doc frontmatter
+ */
+
+use crate::core::frontmatter::DocFrontmatter;
+use crate::core::sections::SectionSelector;
+use crate::types::CursorSplitRule;
+use anyhow::{Context, Result};
+use glob::Pattern;
+
+/// Check if a doc path matches any of the given file_patterns globs
+pub fn matches_any_pattern(file_name: &str, patterns: &[String]) -> Result<bool> {
+    for pattern_str in patterns {
+        let pattern = Pattern::new(pattern_str)
+            .with_context(|| format!("Invalid file_patterns pattern: {pattern_str}"))?;
+        if pattern.matches(file_name) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Find the first Cursor-style rule whose file_patterns match the doc path
+pub fn find_cursor_rule<'a>(
+    rules: &'a [CursorSplitRule],
+    file_name: &str,
+) -> Result<Option<&'a CursorSplitRule>> {
+    for rule in rules {
+        if matches_any_pattern(file_name, &rule.file_patterns)? {
+            return Ok(Some(rule));
+        }
+    }
+    Ok(None)
+}
+
+/// Resolve the effective Cursor-style rule for a doc
+/// Frontmatter values take precedence; the matching split_config rule provides defaults
+pub fn resolve_cursor_rule(
+    matched_rule: Option<&CursorSplitRule>,
+    frontmatter: &DocFrontmatter,
+) -> Option<CursorSplitRule> {
+    if !frontmatter.has_rule_metadata() {
+        return matched_rule.cloned();
+    }
+
+    let mut rule = matched_rule.cloned().unwrap_or(CursorSplitRule {
+        file_patterns: Vec::new(),
+        always_apply: None,
+        globs: None,
+        description: None,
+        manual: None,
+        sections: None,
+    });
+    if frontmatter.always_apply.is_some() {
+        rule.always_apply = frontmatter.always_apply;
+    }
+    if frontmatter.globs.is_some() {
+        rule.globs = frontmatter.globs.clone();
+    }
+    if frontmatter.description.is_some() {
+        rule.description = frontmatter.description.clone();
+    }
+    Some(rule)
+}
+
+/// Content of a doc for a matched rule: only the selected sections when the rule has a
+/// sections selector, otherwise the whole doc
+/// Returns None when the rule selects no section of a non-empty doc
+pub fn select_rule_content(
+    matched_rule: Option<&CursorSplitRule>,
+    content: &str,
+) -> Result<Option<String>> {
+    let Some(sections) = matched_rule.and_then(|rule| rule.sections.as_ref()) else {
+        return Ok(Some(content.to_string()));
+    };
+    let selected = SectionSelector::new(sections)?.select_from(content);
+    if selected.trim().is_empty() && !content.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(selected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(patterns: &[&str]) -> CursorSplitRule {
+        CursorSplitRule {
+            file_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            always_apply: None,
+            globs: None,
+            description: None,
+            manual: None,
+            sections: None,
+        }
+    }
+
+    #[test]
+    fn test_matches_any_pattern() {
+        let patterns =
+            |patterns: &[&str]| -> Vec<String> { patterns.iter().map(|p| p.to_string()).collect() };
+
+        // Wildcards before and after
+        assert!(matches_any_pattern("architecture.md", &patterns(&["*architecture*"])).unwrap());
+        assert!(matches_any_pattern("guides/rust-guide.md", &patterns(&["*rust*"])).unwrap());
+        // Only before / only after
+        assert!(matches_any_pattern("test.md", &patterns(&["*test.md"])).unwrap());
+        assert!(!matches_any_pattern("test-file.md", &patterns(&["*test.md"])).unwrap());
+        assert!(matches_any_pattern("config.md", &patterns(&["config*"])).unwrap());
+        assert!(!matches_any_pattern("my-config.md", &patterns(&["config*"])).unwrap());
+        // Exact match and wildcards in the middle
+        assert!(matches_any_pattern("exact.md", &patterns(&["exact.md"])).unwrap());
+        assert!(!matches_any_pattern("exact-file.md", &patterns(&["exact.md"])).unwrap());
+        assert!(matches_any_pattern("api/errors.md", &patterns(&["api/*.md"])).unwrap());
+
+        let error = matches_any_pattern("a.md", &patterns(&["[a"])).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Invalid file_patterns pattern: [a"));
+    }
+
+    #[test]
+    fn test_find_and_resolve_cursor_rule() {
+        let rules = vec![rule(&["*api*"]), rule(&["*"])];
+        let matched = find_cursor_rule(&rules, "api.md").unwrap();
+        assert_eq!(matched.unwrap().file_patterns, vec!["*api*"]);
+
+        // Frontmatter overrides the matched rule
+        let frontmatter = DocFrontmatter {
+            description: Some("API rules".to_string()),
+            ..Default::default()
+        };
+        let resolved = resolve_cursor_rule(matched, &frontmatter).unwrap();
+        assert_eq!(resolved.description.as_deref(), Some("API rules"));
+        assert_eq!(resolved.file_patterns, vec!["*api*"]);
+
+        // No rule and no frontmatter metadata
+        assert!(resolve_cursor_rule(None, &DocFrontmatter::default()).is_none());
+    }
+
+    #[test]
+    fn test_select_rule_content() {
+        let content = "# Doc\n\n## Auth\n\nTokens\n\n## Storage\n\nFiles\n";
+        let mut auth = rule(&["*"]);
+        auth.sections = Some(vec!["Auth".to_string()]);
+        assert_eq!(
+            select_rule_content(Some(&auth), content).unwrap().unwrap(),
+            SectionSelector::new(&["Auth".to_string()])
+                .unwrap()
+                .select_from(content)
+        );
+
+        auth.sections = Some(vec!["Missing".to_string()]);
+        assert!(select_rule_content(Some(&auth), content).unwrap().is_none());
+        assert_eq!(
+            select_rule_content(None, content).unwrap().as_deref(),
+            Some(content)
+        );
+    }
+}
@@ -0,0 +1,335 @@
+/*!
+ * AI Context Management Tool - Template Variables
+ *
+ * Substitutes `{{ name }}` expressions in source docs
+ */
+
+use crate::core::code_fence::CodeFenceTracker;
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Variables available to `{{ name }}` expressions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateContext {
+    variables: BTreeMap<String, String>,
+}
+
+impl TemplateContext {
+    /// Build the context for an agent
+    /// Priority: user-defined variables > agent.name > project values detected in `project_dir`
+    pub fn new(
+        agent: Option<&str>,
+        user_variables: &BTreeMap<String, serde_yaml::Value>,
+        project_dir: &Path,
+    ) -> Result<Self> {
+        let mut variables = detect_project_variables(project_dir);
+        if let Some(agent) = agent {
+            variables.insert("agent.name".to_string(), agent.to_string());
+        }
+        for (name, value) in user_variables {
+            let value = match value {
+                serde_yaml::Value::String(s) => s.clone(),
+                serde_yaml::Value::Number(n) => n.to_string(),
+                serde_yaml::Value::Bool(b) => b.to_string(),
+                _ => bail!("Template variable '{name}' must be a string, number or boolean"),
+            };
+            variables.insert(name.clone(), value);
+        }
+        Ok(Self { variables })
+    }
+
+    /// Replace `{{ name }}` expressions; `\{{` outputs literal braces
+    /// `{{ env.NAME }}` reads an environment variable
+    /// Fenced code blocks and inline code spans are left untouched
+    /// (e.g. GitHub Actions `${{ ... }}` snippets)
+    pub fn render(&self, content: &str) -> Result<String> {
+        let mut output = String::with_capacity(content.len());
+        let mut fence = CodeFenceTracker::new();
+        let mut segment_start = 0;
+        let mut segment_line = 1;
+        let mut offset = 0;
+
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            if fence.update(line) {
+                // Render the text before the code block, then copy the code verbatim
+                output.push_str(&self.render_text(&content[segment_start..offset], segment_line)?);
+                output.push_str(line);
+                segment_start = offset + line.len();
+                segment_line = index + 2;
+            }
+            offset += line.len();
+        }
+
+        output.push_str(&self.render_text(&content[segment_start..], segment_line)?);
+        Ok(output)
+    }
+
+    /// Render text outside code blocks; `first_line` is its line number in the doc
+    fn render_text(&self, content: &str, first_line: usize) -> Result<String> {
+        let mut output = String::with_capacity(content.len());
+        let mut rest = content;
+        let mut line_number = first_line;
+
+        while let Some(start) = rest.find("{{") {
+            if let Some(tick) = rest[..start].find('`') {
+                // Copy an inline code span verbatim (closed on the same line)
+                let (before, after) = rest.split_at(tick);
+                line_number += before.matches('\n').count();
+                output.push_str(before);
+                let ticks = after.chars().take_while(|&c| c == '`').count();
+                let line_end = after.find('\n').unwrap_or(after.len());
+                let end = after[ticks..line_end]
+                    .find(&"`".repeat(ticks))
+                    .map_or(ticks, |end| 2 * ticks + end);
+                output.push_str(&after[..end]);
+                rest = &after[end..];
+                continue;
+            }
+
+            let (before, after) = rest.split_at(start);
+            line_number += before.matches('\n').count();
+
+            // Escaped: \{{ -> {{
+            if let Some(literal) = before.strip_suffix('\\') {
+                output.push_str(literal);
+                output.push_str("{{");
+                rest = &after[2..];
+                continue;
+            }
+            output.push_str(before);
+
+            let Some(end) = after.find("}}") else {
+                bail!("line {line_number}: unclosed template expression");
+            };
+            let expression = after[2..end].trim();
+            if expression.is_empty()
+                || !expression
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+            {
+                bail!("line {line_number}: invalid template expression '{expression}'");
+            }
+
+            output.push_str(&self.lookup(line_number, expression)?);
+            line_number += after[..end].matches('\n').count();
+            rest = &after[end + 2..];
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    fn lookup(&self, line_number: usize, name: &str) -> Result<String> {
+        if let Some(value) = self.variables.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some(env_name) = name.strip_prefix("env.") {
+            return std::env::var(env_name).map_err(|_| {
+                anyhow::anyhow!("line {line_number}: environment variable '{env_name}' is not set")
+            });
+        }
+        bail!("line {line_number}: undefined template variable '{name}'")
+    }
+}
+
+/// Detect `project.*` values from Cargo.toml or package.json
+pub fn detect_project_variables(project_dir: &Path) -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+
+    if let Ok(manifest) = fs::read_to_string(project_dir.join("Cargo.toml")) {
+        for (key, value) in cargo_package_values(&manifest) {
+            let name = match key.as_str() {
+                "name" | "version" | "description" => key,
+                "rust-version" => "rust_version".to_string(),
+                _ => continue,
+            };
+            variables.insert(format!("project.{name}"), value);
+        }
+    } else if let Ok(manifest) = fs::read_to_string(project_dir.join("package.json")) {
+        // JSON is valid YAML, so serde_yaml can read package.json
+        if let Ok(serde_yaml::Value::Mapping(package)) = serde_yaml::from_str(&manifest) {
+            for key in ["name", "version", "description"] {
+                if let Some(serde_yaml::Value::String(value)) = package.get(key) {
+                    variables.insert(format!("project.{key}"), value.clone());
+                }
+            }
+        }
+    }
+
+    variables
+}
+
+/// Read `key = "value"` pairs of the [package] table
+fn cargo_package_values(manifest: &str) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    let mut in_package = false;
+
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        if let Some(value) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            values.push((key.trim().to_string(), value.to_string()));
+        }
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn context(variables: &[(&str, &str)]) -> TemplateContext {
+        let user_variables = variables
+            .iter()
+            .map(|(k, v)| (k.to_string(), serde_yaml::Value::String(v.to_string())))
+            .collect();
+        let temp_dir = tempdir().unwrap();
+        TemplateContext::new(Some("claude"), &user_variables, temp_dir.path()).unwrap()
+    }
+
+    #[test]
+    fn test_render_variables() {
+        let context = context(&[("default_branch", "main")]);
+        let rendered = context
+            .render("Agent: {{agent.name}}\nBranch: {{ default_branch }}")
+            .unwrap();
+        assert_eq!(rendered, "Agent: claude\nBranch: main");
+    }
+
+    #[test]
+    fn test_render_env_variable() {
+        std::env::set_var("AICM_TEMPLATE_TEST", "from-env");
+        let rendered = context(&[]).render("{{ env.AICM_TEMPLATE_TEST }}").unwrap();
+        assert_eq!(rendered, "from-env");
+
+        let error = context(&[])
+            .render("{{ env.AICM_TEMPLATE_TEST_UNSET }}")
+            .unwrap_err();
+        assert!(error.to_string().contains("AICM_TEMPLATE_TEST_UNSET"));
+    }
+
+    #[test]
+    fn test_render_escape() {
+        let rendered = context(&[]).render("run: $\\{{ github.ref }}").unwrap();
+        assert_eq!(rendered, "run: ${{ github.ref }}");
+    }
+
+    #[test]
+    fn test_render_skips_fenced_code() {
+        let context = context(&[("default_branch", "main")]);
+        let content = "Branch: {{ default_branch }}\n\n```yaml\non:\n  push:\n    branches: [${{ github.ref }}]\n```\n\nAgain: {{ default_branch }}\n";
+        assert_eq!(
+            context.render(content).unwrap(),
+            "Branch: main\n\n```yaml\non:\n  push:\n    branches: [${{ github.ref }}]\n```\n\nAgain: main\n"
+        );
+
+        // Line numbers count the skipped code block
+        let error = context
+            .render("```\n{{ a }}\n```\n{{ missing }}")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4: undefined template variable 'missing'"
+        );
+    }
+
+    #[test]
+    fn test_render_skips_inline_code() {
+        let context = context(&[("project", "aicm")]);
+        let content = "Write `{{ project }}` to get {{ project }}.\nUse ``a `{{ x }}` b`` and `ok`\n`unclosed {{ project }}\n{{ missing }}";
+        let error = context.render(content).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4: undefined template variable 'missing'"
+        );
+
+        let content = "Write `{{ project }}` to get {{ project }}.\nUse ``a `{{ x }}` b`` and `ok`\n`unclosed {{ project }}\n";
+        assert_eq!(
+            context.render(content).unwrap(),
+            "Write `{{ project }}` to get aicm.\nUse ``a `{{ x }}` b`` and `ok`\n`unclosed aicm\n"
+        );
+    }
+
+    #[test]
+    fn test_render_errors_report_line() {
+        let error = context(&[]).render("a\nb {{ missing }}").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: undefined template variable 'missing'"
+        );
+
+        let error = context(&[]).render("{{ open").unwrap_err();
+        assert!(error.to_string().contains("line 1: unclosed"));
+
+        let error = context(&[]).render("{{ a b }}").unwrap_err();
+        assert!(error.to_string().contains("invalid template expression"));
+    }
+
+    #[test]
+    fn test_detect_cargo_project_variables() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"1.2.3\"\nrust-version = \"1.74\"\n\n[dependencies]\nname = \"ignored\"\n",
+        )
+        .unwrap();
+
+        let variables = detect_project_variables(temp_dir.path());
+        assert_eq!(variables["project.name"], "demo");
+        assert_eq!(variables["project.version"], "1.2.3");
+        assert_eq!(variables["project.rust_version"], "1.74");
+        assert_eq!(variables.len(), 3);
+    }
+
+    #[test]
+    fn test_detect_package_json_variables() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "web-app", "version": "0.1.0", "private": true}"#,
+        )
+        .unwrap();
+
+        let variables = detect_project_variables(temp_dir.path());
+        assert_eq!(variables["project.name"], "web-app");
+        assert_eq!(variables["project.version"], "0.1.0");
+    }
+
+    #[test]
+    fn test_user_variables_override_project_values() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )
+        .unwrap();
+        let user_variables = BTreeMap::from([
+            (
+                "project.name".to_string(),
+                serde_yaml::Value::String("Demo".to_string()),
+            ),
+            ("msrv".to_string(), serde_yaml::from_str("1.74").unwrap()),
+        ]);
+
+        let context = TemplateContext::new(None, &user_variables, temp_dir.path()).unwrap();
+        assert_eq!(
+            context.render("{{ project.name }} {{ msrv }}").unwrap(),
+            "Demo 1.74"
+        );
+        assert!(context.render("{{ agent.name }}").is_err());
+    }
+}
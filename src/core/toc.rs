@@ -0,0 +1,174 @@
+/*!
+ * AI Context Management Tool - Table of Contents
+ *
+ * Builds a linked table of contents from the headings of merged output
+ */
+
+use crate::core::headings::scan_headings;
+use std::collections::HashMap;
+
+/// Deepest heading level listed in the table of contents
+pub const TOC_MAX_LEVEL: usize = 3;
+
+/// Prepend a table of contents to merged content (unchanged if it has no headings)
+pub fn insert_toc(content: &str) -> String {
+    let toc = generate_toc(content);
+    if toc.is_empty() {
+        content.to_string()
+    } else {
+        format!("{toc}\n\n{content}")
+    }
+}
+
+/// Build a nested list of links to the H1-H3 headings of the content
+/// Anchors follow GitHub slug rules, with `-1`, `-2`, ... suffixes for duplicates
+pub fn generate_toc(content: &str) -> String {
+    let mut slugs = SlugCounter::default();
+    let headings: Vec<(usize, String, String)> = scan_headings(content)
+        .filter_map(|(line, level)| {
+            let level = level?;
+            let text = heading_text(line);
+            // Every heading takes a slug so duplicate suffixes match GitHub
+            let anchor = slugs.unique(&text);
+            (level <= TOC_MAX_LEVEL && !text.is_empty()).then_some((level, text, anchor))
+        })
+        .collect();
+
+    let Some(min_level) = headings.iter().map(|(level, _, _)| *level).min() else {
+        return String::new();
+    };
+
+    let mut toc = String::from("**Table of Contents**\n");
+    for (level, text, anchor) in headings {
+        let indent = "  ".repeat(level - min_level);
+        toc.push_str(&format!("\n{indent}- [{text}](#{anchor})"));
+    }
+    toc
+}
+
+/// Convert heading text to a GitHub anchor
+/// Lowercased, punctuation removed (except `-` and `_`), spaces replaced with `-`
+pub fn github_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// List the GitHub anchors of every heading of the content, in order
+pub fn heading_anchors(content: &str) -> Vec<String> {
+    let mut slugs = SlugCounter::default();
+    scan_headings(content)
+        .filter(|(_, level)| level.is_some())
+        .map(|(line, _)| slugs.unique(&heading_text(line)))
+        .collect()
+}
+
+/// Assigns unique anchors the way GitHub does for repeated headings
+#[derive(Debug, Default)]
+pub(crate) struct SlugCounter {
+    seen: HashMap<String, usize>,
+}
+
+impl SlugCounter {
+    pub(crate) fn unique(&mut self, text: &str) -> String {
+        let slug = github_slug(text);
+        let count = self.seen.entry(slug.clone()).or_insert(0);
+        let unique = if *count == 0 {
+            slug
+        } else {
+            format!("{slug}-{count}")
+        };
+        *count += 1;
+        unique
+    }
+}
+
+/// Get the displayed text of a heading line (markers, closing `#`s, link targets and code spans removed)
+pub(crate) fn heading_text(line: &str) -> String {
+    let text = line.trim().trim_start_matches('#').trim();
+    // A closing `#` sequence must be preceded by a space; otherwise it belongs to the text
+    let without_closing = text.trim_end_matches('#');
+    let text = if without_closing.is_empty() || without_closing.ends_with(' ') {
+        without_closing.trim_end()
+    } else {
+        text
+    };
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text.trim();
+    while let Some(start) = rest.find('[') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find("](").and_then(|mid| {
+            after[mid + 2..]
+                .find(')')
+                .map(|end| (mid, mid + 2 + end + 1))
+        }) {
+            Some((mid, end)) => {
+                output.push_str(&after[..mid]);
+                rest = &after[end..];
+            }
+            None => {
+                output.push('[');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output.replace(['`', '*'], "").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_slug() {
+        assert_eq!(github_slug("Getting Started"), "getting-started");
+        assert_eq!(github_slug("What's new in v1.2?"), "whats-new-in-v12");
+        assert_eq!(
+            github_slug("snake_case & kebab-case"),
+            "snake_case--kebab-case"
+        );
+        assert_eq!(github_slug("日本語 見出し"), "日本語-見出し");
+    }
+
+    #[test]
+    fn test_heading_text() {
+        assert_eq!(heading_text("## Setup ##\n"), "Setup");
+        assert_eq!(heading_text("# C#"), "C#");
+        assert_eq!(
+            heading_text("# Use `cargo` with [Rust](https://rust-lang.org)"),
+            "Use cargo with Rust"
+        );
+    }
+
+    #[test]
+    fn test_generate_toc_nesting_and_duplicates() {
+        let content = "# Guide\n\n## Setup\n\n```sh\n# not a heading\n```\n\n#### Deep\n\n# Testing\n\n## Setup\n\n### Setup\n";
+        assert_eq!(
+            generate_toc(content),
+            "**Table of Contents**\n\n- [Guide](#guide)\n  - [Setup](#setup)\n- [Testing](#testing)\n  - [Setup](#setup-1)\n    - [Setup](#setup-2)"
+        );
+    }
+
+    #[test]
+    fn test_generate_toc_relative_levels() {
+        assert_eq!(
+            generate_toc("## A\n### B\n"),
+            "**Table of Contents**\n\n- [A](#a)\n  - [B](#b)"
+        );
+    }
+
+    #[test]
+    fn test_insert_toc_without_headings() {
+        assert_eq!(insert_toc("Plain text"), "Plain text");
+        assert!(insert_toc("# Title\nText").ends_with("\n\n# Title\nText"));
+    }
+}
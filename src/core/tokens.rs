@@ -0,0 +1,211 @@
+/*!
+ * AI Context Management Tool - Token Estimation
+ *
+ * Approximate token counts of generated files and source docs for context budgets
+ */
+
+use crate::core::markdown_merger::SourceDoc;
+use crate::types::GeneratedFile;
+
+/// Average characters per token of English words and identifiers
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Estimate the token count of text with a built-in tokenizer approximation
+/// Word runs count one token per 4 characters; punctuation and CJK characters count one each
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut word_len: usize = 0;
+
+    for c in text.chars() {
+        if c.is_alphanumeric() && !is_cjk(c) {
+            word_len += 1;
+            continue;
+        }
+        tokens += word_len.div_ceil(CHARS_PER_TOKEN);
+        word_len = 0;
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+
+    tokens + word_len.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// CJK ideographs, kana and hangul are tokenized about one token per character
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x2E80..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF)
+}
+
+/// Estimated token count of a single file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenCount {
+    /// Generated file path or source doc path
+    pub path: String,
+    /// Estimated tokens
+    pub tokens: usize,
+}
+
+/// Token statistics of one agent's output
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenReport {
+    /// Agent name
+    pub agent: String,
+    /// Generated files, in generation order
+    pub files: Vec<TokenCount>,
+    /// Source docs, largest first
+    pub docs: Vec<TokenCount>,
+    /// Configured budget of the agent
+    pub max_tokens: Option<usize>,
+}
+
+impl TokenReport {
+    /// Estimate tokens of an agent's generated files and the source docs they were built from
+    pub fn build(
+        agent: &str,
+        files: &[GeneratedFile],
+        docs: &[SourceDoc],
+        max_tokens: Option<usize>,
+    ) -> Self {
+        // Support files (agent settings, manifests) are not context
+        let files = files
+            .iter()
+            .filter(|file| !file.support)
+            .map(|file| TokenCount {
+                path: file.path.clone(),
+                tokens: estimate_tokens(&file.content),
+            })
+            .collect();
+        let mut docs: Vec<TokenCount> = docs
+            .iter()
+            .map(|doc| TokenCount {
+                path: doc.path.clone(),
+                tokens: estimate_tokens(&doc.content),
+            })
+            .collect();
+        docs.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.path.cmp(&b.path)));
+
+        Self {
+            agent: agent.to_string(),
+            files,
+            docs,
+            max_tokens,
+        }
+    }
+
+    /// Total estimated tokens of the generated files
+    pub fn total_tokens(&self) -> usize {
+        self.files.iter().map(|file| file.tokens).sum()
+    }
+
+    /// Message describing the exceeded budget (None if within budget or no budget is set)
+    pub fn budget_error(&self) -> Option<String> {
+        let max_tokens = self.max_tokens?;
+        let total = self.total_tokens();
+        (total > max_tokens).then(|| {
+            format!(
+                "{}: generated output is ~{total} tokens, over max_tokens ({max_tokens})",
+                self.agent
+            )
+        })
+    }
+
+    /// Render generated files and the `top` largest source docs as aligned lines
+    pub fn render(&self, top: usize) -> String {
+        let total = self.total_tokens();
+        let docs = &self.docs[..self.docs.len().min(top)];
+        let width = self
+            .files
+            .iter()
+            .chain(docs)
+            .map(|count| count.path.len())
+            .max()
+            .unwrap_or(0)
+            .max("Total".len());
+
+        let mut lines = vec!["Generated files:".to_string()];
+        for file in &self.files {
+            lines.push(format!("  {:<width$}  ~{} tokens", file.path, file.tokens));
+        }
+        let budget = match self.max_tokens {
+            Some(max_tokens) => format!(
+                " / max_tokens {max_tokens} ({}%)",
+                total * 100 / max_tokens.max(1)
+            ),
+            None => String::new(),
+        };
+        lines.push(format!("  {:<width$}  ~{total} tokens{budget}", "Total"));
+
+        if !docs.is_empty() {
+            lines.push("Largest source docs:".to_string());
+            let doc_total: usize = self.docs.iter().map(|doc| doc.tokens).sum();
+            for doc in docs {
+                lines.push(format!(
+                    "  {:<width$}  ~{} tokens ({}%)",
+                    doc.path,
+                    doc.tokens,
+                    doc.tokens * 100 / doc_total.max(1)
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::frontmatter::DocFrontmatter;
+
+    fn doc(path: &str, content: &str) -> SourceDoc {
+        SourceDoc {
+            path: path.to_string(),
+            content: content.to_string(),
+            frontmatter: DocFrontmatter::default(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("Use cargo test"), 4);
+        assert_eq!(estimate_tokens("# Title\n\n- item."), 6);
+        assert_eq!(estimate_tokens("日本語"), 3);
+    }
+
+    #[test]
+    fn test_report_sorts_docs_and_checks_budget() {
+        let files = vec![
+            GeneratedFile::new("CLAUDE.md".to_string(), "a b c d e".to_string()),
+            GeneratedFile::support(".aicm-manifest".to_string(), "a b c".to_string()),
+        ];
+        let docs = vec![doc("small.md", "a"), doc("large.md", "a b c d")];
+
+        // Support files are left out of the report
+        let report = TokenReport::build("claude", &files, &docs, Some(4));
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.total_tokens(), 5);
+        assert_eq!(report.docs[0].path, "large.md");
+        assert_eq!(
+            report.budget_error().unwrap(),
+            "claude: generated output is ~5 tokens, over max_tokens (4)"
+        );
+
+        let report = TokenReport::build("claude", &files, &docs, None);
+        assert!(report.budget_error().is_none());
+    }
+
+    #[test]
+    fn test_render_report() {
+        let files = vec![GeneratedFile::new(
+            "CLAUDE.md".to_string(),
+            "a b c d".to_string(),
+        )];
+        let docs = vec![doc("a.md", "a b c"), doc("b.md", "d")];
+
+        let report = TokenReport::build("claude", &files, &docs, Some(8));
+        assert_eq!(
+            report.render(1),
+            "Generated files:\n  CLAUDE.md  ~4 tokens\n  Total      ~4 tokens / max_tokens 8 (50%)\nLargest source docs:\n  a.md       ~3 tokens (75%)"
+        );
+    }
+}
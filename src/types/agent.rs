@@ -13,12 +13,29 @@ pub struct GeneratedFile {
     pub path: String,
     /// File content
     pub content: String,
+    /// Tool settings or aicm bookkeeping rather than context
+    /// (not counted toward token budgets and stats, not secret scanned)
+    #[serde(default)]
+    pub support: bool,
 }
 
 impl GeneratedFile {
     /// Create a new GeneratedFile
     pub fn new(path: String, content: String) -> Self {
-        Self { path, content }
+        Self {
+            path,
+            content,
+            support: false,
+        }
+    }
+
+    /// Create a support file (e.g. an agent's settings file or aicm's manifest)
+    pub fn support(path: String, content: String) -> Self {
+        Self {
+            path,
+            content,
+            support: true,
+        }
     }
 }
 
@@ -35,6 +52,10 @@ mod tests {
 
         assert_eq!(file.path, ".cursor/rules/context.mdc");
         assert_eq!(file.content, "# Test Content");
+        assert!(!file.support);
+
+        let file = GeneratedFile::support(".aider.conf.yml".to_string(), "read: []".to_string());
+        assert!(file.support);
     }
 
     #[test]
@@ -42,6 +63,7 @@ mod tests {
         let file = GeneratedFile {
             path: ".cursor/rules/test.mdc".to_string(),
             content: "---\ndescription: Test\n---\n\n# Test Content".to_string(),
+            support: false,
         };
 
         let yaml = serde_yaml::to_string(&file).unwrap();
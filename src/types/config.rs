@@ -5,6 +5,8 @@
  */
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// Import file configuration for Claude agent
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -27,8 +29,77 @@ pub struct AIContextConfig {
     /// Whether to include filename headers in merged mode (default: false)
     #[serde(default)]
     pub include_filenames: Option<bool>,
+    /// How doc headings are placed under filename headers (optional, default: keep)
+    #[serde(default)]
+    pub heading_mode: Option<HeadingMode>,
+    /// Whether to add a table of contents to merged output (optional, default: false)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Split large docs into one split-mode output per section (optional)
+    #[serde(default)]
+    pub split_sections: Option<SectionSplitConfig>,
+    /// What to do when an agent exceeds its max_tokens budget (optional, default: warn)
+    #[serde(default)]
+    pub max_tokens_action: Option<BudgetAction>,
     /// Base documentation directory
     pub base_docs_dir: String,
+    /// Documentation directories merged in order (optional, replaces base_docs_dir)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Shared doc packs vendored from git repositories or archives (optional)
+    #[serde(default)]
+    pub packs: Option<Vec<PackConfig>>,
+    /// Directory of the loaded configuration file (set by the loader, not read from YAML)
+    #[serde(skip)]
+    pub config_dir: Option<PathBuf>,
+    /// Rewrite relative links in docs for each generated file location (optional, default: false)
+    #[serde(default)]
+    pub rewrite_links: Option<bool>,
+    /// Secret scanning of generated files (optional, enabled by default)
+    #[serde(default)]
+    pub secret_scan: Option<SecretScanConfig>,
+    /// How symbolic links in docs directories are handled (optional, default: follow)
+    #[serde(default)]
+    pub symlinks: Option<SymlinkPolicy>,
+    /// Maximum directory depth scanned below a docs directory (optional, default: 32)
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// Maximum size in bytes of a single source doc (optional, default: 1 MiB)
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+    /// File extensions loaded as source docs (optional, default: [md])
+    #[serde(default)]
+    pub source_extensions: Option<Vec<String>>,
+    /// Encoding used for source docs that are not valid UTF-8 (optional, e.g. shift_jis)
+    #[serde(default)]
+    pub source_encoding: Option<String>,
+    /// Convert CRLF/CR line endings of source docs to LF (optional, default: true)
+    #[serde(default)]
+    pub normalize_line_endings: Option<bool>,
+    /// Line ending of generated files (optional, default: lf)
+    #[serde(default)]
+    pub output_eol: Option<LineEnding>,
+    /// How frontmatter keys of source docs are handled (optional, default: strip)
+    #[serde(default)]
+    pub source_frontmatter: Option<FrontmatterMode>,
+    /// Whether `{{ name }}` template variables in docs are substituted (optional, default: false)
+    #[serde(default)]
+    pub templating: Option<bool>,
+    /// User-defined template variables
+    #[serde(default)]
+    pub variables: Option<BTreeMap<String, serde_yaml::Value>>,
+    /// Doc path globs in output order (unlisted docs follow in path order)
+    #[serde(default)]
+    pub order: Option<Vec<String>>,
+    /// Doc path globs to load (optional, default: all .md files)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop from every doc (optional)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
     /// Agent enable/disable settings
     pub agents: AgentConfig,
 }
@@ -43,6 +114,174 @@ pub enum OutputMode {
     Split,
 }
 
+/// Action taken when generated output exceeds an agent's max_tokens budget
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetAction {
+    /// Print a warning and still write the files
+    Warn,
+    /// Fail generation for the agent
+    Fail,
+}
+
+/// Line ending of generated files
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+/// Section splitting of large docs in split mode
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SectionSplitConfig {
+    /// Heading level that starts a new section (optional, default: 2)
+    #[serde(default)]
+    pub heading_level: Option<usize>,
+    /// Only docs above this estimated token count are split (optional, default: 0)
+    #[serde(default)]
+    pub min_tokens: Option<usize>,
+}
+
+impl SectionSplitConfig {
+    /// Get heading level that starts a new section (default: 2, limited to 1-6)
+    pub fn get_heading_level(&self) -> usize {
+        self.heading_level.unwrap_or(2).clamp(1, 6)
+    }
+
+    /// Get estimated token count a doc must exceed to be split (default: 0)
+    pub fn get_min_tokens(&self) -> usize {
+        self.min_tokens.unwrap_or(0)
+    }
+}
+
+/// Secret scanning configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SecretScanConfig {
+    /// Scan generated files before writing (optional, default: true)
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Report high-entropy strings (optional, default: true)
+    #[serde(default)]
+    pub entropy: Option<bool>,
+    /// Additional named regexes
+    #[serde(default)]
+    pub patterns: Vec<SecretPattern>,
+    /// Regexes for matches that are not secrets
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+/// Custom secret pattern
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SecretPattern {
+    pub name: String,
+    pub regex: String,
+}
+
+/// Symbolic link handling while scanning docs directories
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinkPolicy {
+    /// Follow all symbolic links (cycles are detected and skipped)
+    Follow,
+    /// Ignore symbolic links
+    Skip,
+    /// Follow symbolic links whose target is inside the docs directory
+    FollowWithinRoot,
+}
+
+/// Heading normalization types for filename headers in merged mode
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadingMode {
+    /// Paste docs as-is under `# path` headers
+    Keep,
+    /// Demote doc headings one level under `# path` headers
+    Demote,
+    /// Use the doc's own H1 as the section header instead of the path
+    DocTitle,
+}
+
+/// Documentation source entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum DocsSource {
+    /// Directory path or glob (e.g. "ai-context/common")
+    Simple(String),
+    /// Directory path or glob with an output path prefix
+    Advanced(DocsSourceConfig),
+}
+
+/// Detailed documentation source configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DocsSourceConfig {
+    /// Directory path or glob matching directories
+    pub path: String,
+    /// Prefix added to doc paths from this source (used in filename headers and split output names)
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+impl DocsSource {
+    /// Get directory path or glob
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Simple(path) => path,
+            Self::Advanced(config) => &config.path,
+        }
+    }
+
+    /// Get doc path prefix
+    pub fn prefix(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.prefix.as_deref(),
+        }
+    }
+}
+
+/// Shared doc pack configuration (one of `git` or `archive`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackConfig {
+    /// Pack name (cache directory name and default doc path prefix)
+    pub name: String,
+    /// Local git repository path or file:// URL
+    #[serde(default)]
+    pub git: Option<String>,
+    /// Git ref to pin (branch, tag or commit, default: HEAD)
+    #[serde(default, rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Path to a .tar, .tar.gz, .tgz or .zip archive
+    #[serde(default)]
+    pub archive: Option<String>,
+    /// Docs directory inside the pack (optional, default: pack root)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Prefix added to doc paths from this pack (optional, default: pack name)
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+impl PackConfig {
+    /// Get doc path prefix (defaults to the pack name)
+    pub fn get_prefix(&self) -> &str {
+        self.prefix.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Source doc frontmatter handling types
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontmatterMode {
+    /// Drop keys not understood by aicm
+    Strip,
+    /// Carry other keys into generated frontmatter (generated keys win on conflict)
+    Merge,
+}
+
 /// Agent enable/disable settings (extended version)
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentConfig {
@@ -67,6 +306,18 @@ pub struct AgentConfig {
     /// Kiro agent
     #[serde(default)]
     pub kiro: KiroConfig,
+    /// Windsurf agent
+    #[serde(default)]
+    pub windsurf: WindsurfConfig,
+    /// Aider agent
+    #[serde(default)]
+    pub aider: AiderConfig,
+    /// Continue agent (`continue` in config files)
+    #[serde(default, rename = "continue")]
+    pub continue_dev: ContinueConfig,
+    /// Roo Code agent
+    #[serde(default)]
+    pub roo: RooConfig,
 }
 
 /// Cursor agent configuration
@@ -139,6 +390,46 @@ pub enum KiroConfig {
     Advanced(KiroAgentConfig),
 }
 
+/// Windsurf agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum WindsurfConfig {
+    /// Simple configuration (backward compatibility)
+    Simple(bool),
+    /// Detailed configuration
+    Advanced(WindsurfAgentConfig),
+}
+
+/// Aider agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum AiderConfig {
+    /// Simple configuration (backward compatibility)
+    Simple(bool),
+    /// Detailed configuration
+    Advanced(AiderAgentConfig),
+}
+
+/// Continue agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ContinueConfig {
+    /// Simple configuration (backward compatibility)
+    Simple(bool),
+    /// Detailed configuration
+    Advanced(ContinueAgentConfig),
+}
+
+/// Roo agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum RooConfig {
+    /// Simple configuration (backward compatibility)
+    Simple(bool),
+    /// Detailed configuration
+    Advanced(RooAgentConfig),
+}
+
 /// Cursor agent detailed configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CursorAgentConfig {
@@ -154,6 +445,24 @@ pub struct CursorAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
     /// Detailed settings for split mode (optional)
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub split_config: Option<CursorSplitConfig>,
@@ -192,6 +501,9 @@ pub struct CursorSplitRule {
     /// For Manual rule (manual: true)
     #[serde(default)]
     pub manual: Option<bool>,
+    /// Heading texts or globs of the sections output for matching docs (optional, default: whole doc)
+    #[serde(default)]
+    pub sections: Option<Vec<String>>,
 }
 
 /// GitHub split mode rule configuration
@@ -219,6 +531,24 @@ pub struct ClineAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
 }
 
 /// GitHub agent detailed configuration
@@ -236,6 +566,24 @@ pub struct GitHubAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
     /// Detailed settings for split mode (optional)
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub split_config: Option<GitHubSplitConfig>,
@@ -256,6 +604,24 @@ pub struct ClaudeAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
     /// Import files to include in output using @filepath notation
     #[serde(default)]
     pub import_files: Vec<ImportFile>,
@@ -276,6 +642,24 @@ pub struct CodexAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
 }
 
 /// Gemini agent detailed configuration
@@ -293,6 +677,24 @@ pub struct GeminiAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
 }
 
 /// Kiro agent detailed configuration
@@ -310,6 +712,24 @@ pub struct KiroAgentConfig {
     /// Base documentation directory (optional, overrides global setting)
     #[serde(default)]
     pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
     /// Detailed settings for split mode (optional)
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub split_config: Option<KiroSplitConfig>,
@@ -347,61 +767,319 @@ pub enum InclusionMode {
     Manual,
 }
 
-/// Default value: true
-fn default_true() -> bool {
-    true
-}
-
-/// Default agent configurations
-impl Default for CursorConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
-}
-
-impl Default for ClineConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
-}
-
-impl Default for GitHubConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
+/// Windsurf agent detailed configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindsurfAgentConfig {
+    /// Agent enable/disable (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Output mode (optional, overrides global setting)
+    #[serde(default)]
+    pub output_mode: Option<OutputMode>,
+    /// Whether to include filename headers in merged mode (optional, overrides global setting)
+    #[serde(default)]
+    pub include_filenames: Option<bool>,
+    /// Base documentation directory (optional, overrides global setting)
+    #[serde(default)]
+    pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
+    /// Detailed settings for split mode (optional)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub split_config: Option<WindsurfSplitConfig>,
 }
 
-impl Default for ClaudeConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
+/// Windsurf split mode configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindsurfSplitConfig {
+    /// Rule array
+    #[serde(default)]
+    pub rules: Vec<WindsurfSplitRule>,
 }
 
-impl Default for CodexConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
+/// Windsurf split mode rule configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindsurfSplitRule {
+    /// Target Markdown filename patterns
+    pub file_patterns: Vec<String>,
+    /// Activation mode (optional, default: glob with globs, model_decision with description, else always_on)
+    #[serde(default)]
+    pub trigger: Option<WindsurfTrigger>,
+    /// File globs for glob trigger
+    #[serde(default)]
+    pub globs: Option<Vec<String>>,
+    /// Description for model_decision trigger
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
-impl Default for GeminiConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
+/// Activation modes of Windsurf rules
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindsurfTrigger {
+    /// Applied to every Cascade request
+    AlwaysOn,
+    /// Applied when edited files match the globs
+    Glob,
+    /// Applied when Cascade decides the description is relevant
+    ModelDecision,
+    /// Applied only when mentioned with @rule-name
+    Manual,
 }
 
-impl Default for KiroConfig {
-    fn default() -> Self {
-        Self::Simple(false)
-    }
+/// Aider agent detailed configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AiderAgentConfig {
+    /// Agent enable/disable (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Output mode (optional, overrides global setting)
+    #[serde(default)]
+    pub output_mode: Option<OutputMode>,
+    /// Whether to include filename headers in merged mode (optional, overrides global setting)
+    #[serde(default)]
+    pub include_filenames: Option<bool>,
+    /// Base documentation directory (optional, overrides global setting)
+    #[serde(default)]
+    pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
+    /// Conventions file written in merged mode (optional, default: CONVENTIONS.md)
+    #[serde(default)]
+    pub conventions_file: Option<String>,
+    /// Directory of per-doc conventions files in split mode (optional, default: conventions)
+    #[serde(default)]
+    pub conventions_dir: Option<String>,
 }
 
-impl Default for AIContextConfig {
-    fn default() -> Self {
-        Self {
-            version: "1.0".to_string(),
+/// Continue agent detailed configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContinueAgentConfig {
+    /// Agent enable/disable (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Output mode (optional, overrides global setting)
+    #[serde(default)]
+    pub output_mode: Option<OutputMode>,
+    /// Whether to include filename headers in merged mode (optional, overrides global setting)
+    #[serde(default)]
+    pub include_filenames: Option<bool>,
+    /// Base documentation directory (optional, overrides global setting)
+    #[serde(default)]
+    pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
+    /// Detailed settings for split mode (optional, same rules as Cursor)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub split_config: Option<CursorSplitConfig>,
+}
+
+/// Roo agent detailed configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RooAgentConfig {
+    /// Agent enable/disable (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Output mode (optional, overrides global setting)
+    #[serde(default)]
+    pub output_mode: Option<OutputMode>,
+    /// Whether to include filename headers in merged mode (optional, overrides global setting)
+    #[serde(default)]
+    pub include_filenames: Option<bool>,
+    /// Base documentation directory (optional, overrides global setting)
+    #[serde(default)]
+    pub base_docs_dir: Option<String>,
+    /// Documentation sources (optional, overrides base_docs_dir and global docs_sources)
+    #[serde(default)]
+    pub docs_sources: Option<Vec<DocsSource>>,
+    /// Token budget of generated output (optional)
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Whether to add a table of contents to merged output (optional, overrides global setting)
+    #[serde(default)]
+    pub toc: Option<bool>,
+    /// Doc path globs to load (optional, overrides global setting)
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// Doc path globs to skip (optional, added to global setting)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Heading texts or globs of sections to drop (optional, added to global setting)
+    #[serde(default)]
+    pub exclude_sections: Option<Vec<String>>,
+    /// Detailed settings for split mode (optional)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub split_config: Option<RooSplitConfig>,
+}
+
+/// Roo split mode configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RooSplitConfig {
+    /// Rule array
+    #[serde(default)]
+    pub rules: Vec<RooSplitRule>,
+}
+
+/// Roo split mode rule configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RooSplitRule {
+    /// Target Markdown filename patterns
+    pub file_patterns: Vec<String>,
+    /// Mode slug such as code, architect or debug (optional, default: rules for all modes)
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+/// Default value: true
+fn default_true() -> bool {
+    true
+}
+
+/// Default agent configurations
+impl Default for CursorConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for ClineConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for GitHubConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for ClaudeConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for CodexConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for GeminiConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for KiroConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for WindsurfConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for AiderConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for ContinueConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for RooConfig {
+    fn default() -> Self {
+        Self::Simple(false)
+    }
+}
+
+impl Default for AIContextConfig {
+    fn default() -> Self {
+        Self {
+            version: "1.0".to_string(),
             output_mode: Some(OutputMode::Merged), // Default is merged
             include_filenames: Some(false),        // Default is false
+            heading_mode: None,
+            toc: None,
+            split_sections: None,
+            max_tokens_action: None,
             base_docs_dir: "./ai-docs".to_string(),
+            docs_sources: None,
+            packs: None,
+            config_dir: None,
+            rewrite_links: None,
+            secret_scan: None,
+            symlinks: None,
+            max_depth: None,
+            max_file_size: None,
+            source_extensions: None,
+            source_encoding: None,
+            normalize_line_endings: None,
+            output_eol: None,
+            source_frontmatter: None,
+            templating: None,
+            variables: None,
+            order: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             agents: AgentConfig::default(),
         }
     }
@@ -432,6 +1110,18 @@ impl AIContextConfig {
         if self.agents.kiro.is_enabled() {
             agents.push("kiro".to_string());
         }
+        if self.agents.windsurf.is_enabled() {
+            agents.push("windsurf".to_string());
+        }
+        if self.agents.aider.is_enabled() {
+            agents.push("aider".to_string());
+        }
+        if self.agents.continue_dev.is_enabled() {
+            agents.push("continue".to_string());
+        }
+        if self.agents.roo.is_enabled() {
+            agents.push("roo".to_string());
+        }
         agents
     }
 
@@ -440,6 +1130,100 @@ impl AIContextConfig {
         self.output_mode.clone().unwrap_or(OutputMode::Merged)
     }
 
+    /// Get whether relative links are rewritten for generated file locations (default: false)
+    pub fn get_rewrite_links(&self) -> bool {
+        self.rewrite_links.unwrap_or(false)
+    }
+
+    /// Get secret scanning configuration, or None if scanning is disabled
+    pub fn get_secret_scan(&self) -> Option<SecretScanConfig> {
+        let config = self.secret_scan.clone().unwrap_or_default();
+        config.enabled.unwrap_or(true).then_some(config)
+    }
+
+    /// Get symbolic link policy for docs directory scans (default: follow)
+    pub fn get_symlink_policy(&self) -> SymlinkPolicy {
+        self.symlinks.unwrap_or(SymlinkPolicy::Follow)
+    }
+
+    /// Get maximum directory depth scanned below a docs directory (default: 32)
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth.unwrap_or(32)
+    }
+
+    /// Get the project root packs are vendored under: the configuration file's directory
+    /// (default: current directory)
+    pub fn get_packs_root(&self) -> &Path {
+        self.config_dir.as_deref().unwrap_or(Path::new("."))
+    }
+
+    /// Get maximum size in bytes of a single source doc (default: 1 MiB)
+    pub fn get_max_file_size(&self) -> u64 {
+        self.max_file_size.unwrap_or(1024 * 1024)
+    }
+
+    /// Get source doc file extensions without leading dots (default: md)
+    pub fn get_source_extensions(&self) -> Vec<&str> {
+        match &self.source_extensions {
+            Some(extensions) if !extensions.is_empty() => extensions
+                .iter()
+                .map(|ext| ext.trim_start_matches('.'))
+                .collect(),
+            _ => vec!["md"],
+        }
+    }
+
+    /// Get the configured source extension a doc path ends with (longest match)
+    pub fn match_source_extension(&self, path: &str) -> Option<&str> {
+        self.get_source_extensions()
+            .into_iter()
+            .filter(|ext| {
+                path.strip_suffix(ext)
+                    .is_some_and(|stem| stem.len() > 1 && stem.ends_with('.'))
+            })
+            .max_by_key(|ext| ext.len())
+    }
+
+    /// Remove the matched source extension from a doc path for output file names
+    pub fn strip_source_extension<'a>(&self, path: &'a str) -> &'a str {
+        match self.match_source_extension(path) {
+            Some(ext) => &path[..path.len() - ext.len() - 1],
+            None => path,
+        }
+    }
+
+    /// Get whether source doc line endings are normalized to LF (default: true)
+    pub fn get_normalize_line_endings(&self) -> bool {
+        self.normalize_line_endings.unwrap_or(true)
+    }
+
+    /// Get line ending of generated files (default: lf)
+    pub fn get_output_eol(&self) -> LineEnding {
+        self.output_eol.clone().unwrap_or(LineEnding::Lf)
+    }
+
+    /// Get heading normalization mode (default: keep)
+    pub fn get_heading_mode(&self) -> HeadingMode {
+        self.heading_mode.clone().unwrap_or(HeadingMode::Keep)
+    }
+
+    /// Get source doc frontmatter handling (default: strip)
+    pub fn get_source_frontmatter_mode(&self) -> FrontmatterMode {
+        self.source_frontmatter
+            .clone()
+            .unwrap_or(FrontmatterMode::Strip)
+    }
+
+    /// Check whether template variable substitution is enabled (default: false)
+    pub fn is_templating_enabled(&self) -> bool {
+        self.templating.unwrap_or(false)
+    }
+
+    /// Get doc ordering globs (default: none, path order)
+    pub fn get_order_patterns(&self) -> &[String] {
+        self.order.as_deref().unwrap_or_default()
+    }
+
     /// Get effective output mode for specified agent
     /// Priority: agent individual setting > global setting > default (merged)
     pub fn get_effective_output_mode(&self, agent: &str) -> OutputMode {
@@ -463,6 +1247,26 @@ impl AIContextConfig {
             "codex" => OutputMode::Merged,  // Codex is always merged
             "gemini" => OutputMode::Merged, // Gemini is always merged
             "kiro" => OutputMode::Split,    // Kiro is always split
+            "windsurf" => self
+                .agents
+                .windsurf
+                .get_output_mode()
+                .unwrap_or_else(|| self.get_global_output_mode()),
+            "aider" => self
+                .agents
+                .aider
+                .get_output_mode()
+                .unwrap_or_else(|| self.get_global_output_mode()),
+            "continue" => self
+                .agents
+                .continue_dev
+                .get_output_mode()
+                .unwrap_or_else(|| self.get_global_output_mode()),
+            "roo" => self
+                .agents
+                .roo
+                .get_output_mode()
+                .unwrap_or_else(|| self.get_global_output_mode()),
             _ => self.get_global_output_mode(),
         }
     }
@@ -506,10 +1310,119 @@ impl AIContextConfig {
                 .kiro
                 .get_include_filenames()
                 .unwrap_or_else(|| self.include_filenames.unwrap_or(false)),
+            "windsurf" => self
+                .agents
+                .windsurf
+                .get_include_filenames()
+                .unwrap_or_else(|| self.include_filenames.unwrap_or(false)),
+            "aider" => self
+                .agents
+                .aider
+                .get_include_filenames()
+                .unwrap_or_else(|| self.include_filenames.unwrap_or(false)),
+            "continue" => self
+                .agents
+                .continue_dev
+                .get_include_filenames()
+                .unwrap_or_else(|| self.include_filenames.unwrap_or(false)),
+            "roo" => self
+                .agents
+                .roo
+                .get_include_filenames()
+                .unwrap_or_else(|| self.include_filenames.unwrap_or(false)),
             _ => self.include_filenames.unwrap_or(false),
         }
     }
 
+    /// Get configuration of specified agent
+    fn agent_config(&self, agent: &str) -> Option<&dyn AgentConfigTrait> {
+        match agent {
+            "cursor" => Some(&self.agents.cursor),
+            "cline" => Some(&self.agents.cline),
+            "github" => Some(&self.agents.github),
+            "claude" => Some(&self.agents.claude),
+            "codex" => Some(&self.agents.codex),
+            "gemini" => Some(&self.agents.gemini),
+            "kiro" => Some(&self.agents.kiro),
+            "windsurf" => Some(&self.agents.windsurf),
+            "aider" => Some(&self.agents.aider),
+            "continue" => Some(&self.agents.continue_dev),
+            "roo" => Some(&self.agents.roo),
+            _ => None,
+        }
+    }
+
+    /// Get effective include globs for specified agent
+    /// Priority: agent individual setting > global setting > default (all docs)
+    pub fn get_effective_include_patterns(&self, agent: &str) -> &[String] {
+        self.agent_config(agent)
+            .and_then(|config| config.get_include_patterns())
+            .or(self.include.as_ref())
+            .map(|patterns| patterns.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get effective exclude globs for specified agent (global and agent settings combined)
+    pub fn get_effective_exclude_patterns(&self, agent: &str) -> Vec<String> {
+        let mut patterns = self.exclude.clone().unwrap_or_default();
+        if let Some(agent_patterns) = self
+            .agent_config(agent)
+            .and_then(|config| config.get_exclude_patterns())
+        {
+            patterns.extend(agent_patterns.iter().cloned());
+        }
+        patterns
+    }
+
+    /// Get effective excluded sections for specified agent (global sections plus agent sections)
+    pub fn get_effective_exclude_sections(&self, agent: &str) -> Vec<String> {
+        let mut sections = self.exclude_sections.clone().unwrap_or_default();
+        if let Some(agent_sections) = self
+            .agent_config(agent)
+            .and_then(|config| config.get_exclude_sections())
+        {
+            sections.extend(agent_sections.iter().cloned());
+        }
+        sections
+    }
+
+    /// Get effective toc setting for specified agent
+    /// Priority: agent individual setting > global setting > default (false)
+    /// The global setting only applies to single instruction files (claude, github, codex,
+    /// gemini); rule files such as Cursor's context.mdc need the agent's own toc setting
+    pub fn get_effective_toc(&self, agent: &str) -> bool {
+        let global =
+            matches!(agent, "claude" | "github" | "codex" | "gemini") && self.toc.unwrap_or(false);
+        self.agent_config(agent)
+            .and_then(|config| config.get_toc())
+            .unwrap_or(global)
+    }
+
+    /// Get max_tokens budget for specified agent (None: no budget)
+    pub fn get_max_tokens(&self, agent: &str) -> Option<usize> {
+        self.agent_config(agent)
+            .and_then(|config| config.get_max_tokens())
+    }
+
+    /// Get action taken when a max_tokens budget is exceeded (default: warn)
+    pub fn get_max_tokens_action(&self) -> BudgetAction {
+        self.max_tokens_action.clone().unwrap_or(BudgetAction::Warn)
+    }
+
+    /// Get effective docs_sources for specified agent (None: use base_docs_dir)
+    /// Priority: agent docs_sources > agent base_docs_dir > global docs_sources > global base_docs_dir
+    pub fn get_effective_docs_sources(&self, agent: Option<&str>) -> Option<&[DocsSource]> {
+        if let Some(config) = agent.and_then(|agent| self.agent_config(agent)) {
+            if let Some(sources) = config.get_docs_sources() {
+                return Some(sources);
+            }
+            if config.get_base_docs_dir().is_some() {
+                return None;
+            }
+        }
+        self.docs_sources.as_deref()
+    }
+
     /// Get effective base_docs_dir setting for specified agent
     /// Priority: agent individual setting > global setting
     pub fn get_effective_base_docs_dir(&self, agent: &str) -> &str {
@@ -556,6 +1469,30 @@ impl AIContextConfig {
                 .get_base_docs_dir()
                 .map(|s| s.as_str())
                 .unwrap_or(&self.base_docs_dir),
+            "windsurf" => self
+                .agents
+                .windsurf
+                .get_base_docs_dir()
+                .map(|s| s.as_str())
+                .unwrap_or(&self.base_docs_dir),
+            "aider" => self
+                .agents
+                .aider
+                .get_base_docs_dir()
+                .map(|s| s.as_str())
+                .unwrap_or(&self.base_docs_dir),
+            "continue" => self
+                .agents
+                .continue_dev
+                .get_base_docs_dir()
+                .map(|s| s.as_str())
+                .unwrap_or(&self.base_docs_dir),
+            "roo" => self
+                .agents
+                .roo
+                .get_base_docs_dir()
+                .map(|s| s.as_str())
+                .unwrap_or(&self.base_docs_dir),
             _ => &self.base_docs_dir,
         }
     }
@@ -571,6 +1508,18 @@ pub trait AgentConfigTrait {
     fn get_include_filenames(&self) -> Option<bool>;
     /// Get agent individual base_docs_dir setting
     fn get_base_docs_dir(&self) -> Option<&String>;
+    /// Get agent individual docs_sources setting
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>>;
+    /// Get agent individual max_tokens budget
+    fn get_max_tokens(&self) -> Option<usize>;
+    /// Get agent individual toc setting
+    fn get_toc(&self) -> Option<bool>;
+    /// Get agent individual include globs
+    fn get_include_patterns(&self) -> Option<&Vec<String>>;
+    /// Get agent individual exclude globs
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>>;
+    /// Get agent individual excluded sections
+    fn get_exclude_sections(&self) -> Option<&Vec<String>>;
 }
 
 impl AgentConfigTrait for CursorConfig {
@@ -601,6 +1550,48 @@ impl AgentConfigTrait for CursorConfig {
             Self::Advanced(config) => config.base_docs_dir.as_ref(),
         }
     }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
 }
 
 impl AgentConfigTrait for ClineConfig {
@@ -631,9 +1622,605 @@ impl AgentConfigTrait for ClineConfig {
             Self::Advanced(config) => config.base_docs_dir.as_ref(),
         }
     }
-}
 
-impl AgentConfigTrait for GitHubConfig {
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl AgentConfigTrait for GitHubConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl GitHubConfig {
+    /// Get detailed configuration
+    pub fn get_advanced_config(&self) -> Option<&GitHubAgentConfig> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => Some(config),
+        }
+    }
+}
+
+impl KiroConfig {
+    /// Get detailed configuration
+    pub fn get_advanced_config(&self) -> Option<&KiroAgentConfig> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => Some(config),
+        }
+    }
+}
+
+impl AgentConfigTrait for ClaudeConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl AgentConfigTrait for CodexConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl AgentConfigTrait for GeminiConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl AgentConfigTrait for KiroConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl WindsurfConfig {
+    /// Get detailed configuration
+    pub fn get_advanced_config(&self) -> Option<&WindsurfAgentConfig> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => Some(config),
+        }
+    }
+}
+
+impl AgentConfigTrait for WindsurfConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl AiderConfig {
+    /// Get detailed configuration
+    pub fn get_advanced_config(&self) -> Option<&AiderAgentConfig> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => Some(config),
+        }
+    }
+}
+
+impl AgentConfigTrait for AiderConfig {
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Simple(enabled) => *enabled,
+            Self::Advanced(config) => config.enabled,
+        }
+    }
+
+    fn get_output_mode(&self) -> Option<OutputMode> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.output_mode.clone(),
+        }
+    }
+
+    fn get_include_filenames(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include_filenames,
+        }
+    }
+
+    fn get_base_docs_dir(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+        }
+    }
+
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.docs_sources.as_ref(),
+        }
+    }
+
+    fn get_max_tokens(&self) -> Option<usize> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.max_tokens,
+        }
+    }
+
+    fn get_toc(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
+        }
+    }
+
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.include.as_ref(),
+        }
+    }
+
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude.as_ref(),
+        }
+    }
+
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
+        }
+    }
+}
+
+impl ContinueConfig {
+    /// Get detailed configuration
+    pub fn get_advanced_config(&self) -> Option<&ContinueAgentConfig> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => Some(config),
+        }
+    }
+}
+
+impl AgentConfigTrait for ContinueConfig {
     fn is_enabled(&self) -> bool {
         match self {
             Self::Simple(enabled) => *enabled,
@@ -661,59 +2248,61 @@ impl AgentConfigTrait for GitHubConfig {
             Self::Advanced(config) => config.base_docs_dir.as_ref(),
         }
     }
-}
 
-impl GitHubConfig {
-    /// Get detailed configuration
-    pub fn get_advanced_config(&self) -> Option<&GitHubAgentConfig> {
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => Some(config),
+            Self::Advanced(config) => config.docs_sources.as_ref(),
         }
     }
-}
 
-impl KiroConfig {
-    /// Get detailed configuration
-    pub fn get_advanced_config(&self) -> Option<&KiroAgentConfig> {
+    fn get_max_tokens(&self) -> Option<usize> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => Some(config),
+            Self::Advanced(config) => config.max_tokens,
         }
     }
-}
 
-impl AgentConfigTrait for ClaudeConfig {
-    fn is_enabled(&self) -> bool {
+    fn get_toc(&self) -> Option<bool> {
         match self {
-            Self::Simple(enabled) => *enabled,
-            Self::Advanced(config) => config.enabled,
+            Self::Simple(_) => None,
+            Self::Advanced(config) => config.toc,
         }
     }
 
-    fn get_output_mode(&self) -> Option<OutputMode> {
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.output_mode.clone(),
+            Self::Advanced(config) => config.include.as_ref(),
         }
     }
 
-    fn get_include_filenames(&self) -> Option<bool> {
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.include_filenames,
+            Self::Advanced(config) => config.exclude.as_ref(),
         }
     }
 
-    fn get_base_docs_dir(&self) -> Option<&String> {
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
         }
     }
 }
 
-impl AgentConfigTrait for CodexConfig {
+impl RooConfig {
+    /// Get detailed configuration
+    pub fn get_advanced_config(&self) -> Option<&RooAgentConfig> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Advanced(config) => Some(config),
+        }
+    }
+}
+
+impl AgentConfigTrait for RooConfig {
     fn is_enabled(&self) -> bool {
         match self {
             Self::Simple(enabled) => *enabled,
@@ -741,64 +2330,46 @@ impl AgentConfigTrait for CodexConfig {
             Self::Advanced(config) => config.base_docs_dir.as_ref(),
         }
     }
-}
-
-impl AgentConfigTrait for GeminiConfig {
-    fn is_enabled(&self) -> bool {
-        match self {
-            Self::Simple(enabled) => *enabled,
-            Self::Advanced(config) => config.enabled,
-        }
-    }
 
-    fn get_output_mode(&self) -> Option<OutputMode> {
+    fn get_docs_sources(&self) -> Option<&Vec<DocsSource>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.output_mode.clone(),
+            Self::Advanced(config) => config.docs_sources.as_ref(),
         }
     }
 
-    fn get_include_filenames(&self) -> Option<bool> {
+    fn get_max_tokens(&self) -> Option<usize> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.include_filenames,
+            Self::Advanced(config) => config.max_tokens,
         }
     }
 
-    fn get_base_docs_dir(&self) -> Option<&String> {
+    fn get_toc(&self) -> Option<bool> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.base_docs_dir.as_ref(),
-        }
-    }
-}
-
-impl AgentConfigTrait for KiroConfig {
-    fn is_enabled(&self) -> bool {
-        match self {
-            Self::Simple(enabled) => *enabled,
-            Self::Advanced(config) => config.enabled,
+            Self::Advanced(config) => config.toc,
         }
     }
 
-    fn get_output_mode(&self) -> Option<OutputMode> {
+    fn get_include_patterns(&self) -> Option<&Vec<String>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.output_mode.clone(),
+            Self::Advanced(config) => config.include.as_ref(),
         }
     }
 
-    fn get_include_filenames(&self) -> Option<bool> {
+    fn get_exclude_patterns(&self) -> Option<&Vec<String>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.include_filenames,
+            Self::Advanced(config) => config.exclude.as_ref(),
         }
     }
 
-    fn get_base_docs_dir(&self) -> Option<&String> {
+    fn get_exclude_sections(&self) -> Option<&Vec<String>> {
         match self {
             Self::Simple(_) => None,
-            Self::Advanced(config) => config.base_docs_dir.as_ref(),
+            Self::Advanced(config) => config.exclude_sections.as_ref(),
         }
     }
 }
@@ -856,6 +2427,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
         config.agents.cline = ClineConfig::Advanced(ClineAgentConfig {
@@ -863,6 +2440,12 @@ mod tests {
             output_mode: Some(OutputMode::Merged),
             include_filenames: None,
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
         });
 
         let enabled = config.enabled_agents();
@@ -908,6 +2491,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Merged),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
 
@@ -929,6 +2518,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split), // Set but ignored
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             import_files: Vec::new(),
         });
 
@@ -950,6 +2545,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split), // Set but ignored
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
         });
 
         // Codex is always merged
@@ -970,6 +2571,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split), // Set but ignored
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
         });
 
         // Gemini is always merged
@@ -990,6 +2597,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Merged), // Set but ignored
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
 
@@ -1023,6 +2636,333 @@ mod tests {
         assert_eq!(yaml.trim(), "split");
     }
 
+    #[test]
+    fn test_source_frontmatter_mode_parsing() {
+        let config = AIContextConfig::default();
+        assert_eq!(config.get_source_frontmatter_mode(), FrontmatterMode::Strip);
+
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+source_frontmatter: merge
+agents:
+  cursor: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.get_source_frontmatter_mode(), FrontmatterMode::Merge);
+    }
+
+    #[test]
+    fn test_templating_config_parsing() {
+        let config = AIContextConfig::default();
+        assert!(!config.is_templating_enabled());
+
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+templating: true
+variables:
+  default_branch: main
+  msrv: "1.74"
+agents:
+  claude: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.is_templating_enabled());
+        let variables = config.variables.unwrap();
+        assert_eq!(
+            variables["default_branch"],
+            serde_yaml::Value::String("main".to_string())
+        );
+        assert_eq!(variables.len(), 2);
+    }
+
+    #[test]
+    fn test_effective_include_exclude_patterns() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+include: ["*.md"]
+exclude: ["drafts/*"]
+agents:
+  claude: true
+  cursor:
+    include: ["rules/*"]
+    exclude: ["*-human.md"]
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.get_effective_include_patterns("claude"), ["*.md"]);
+        assert_eq!(config.get_effective_include_patterns("cursor"), ["rules/*"]);
+        assert_eq!(
+            config.get_effective_exclude_patterns("claude"),
+            vec!["drafts/*".to_string()]
+        );
+        assert_eq!(
+            config.get_effective_exclude_patterns("cursor"),
+            vec!["drafts/*".to_string(), "*-human.md".to_string()]
+        );
+
+        let config = AIContextConfig::default();
+        assert!(config.get_effective_include_patterns("cursor").is_empty());
+        assert!(config.get_effective_exclude_patterns("cursor").is_empty());
+    }
+
+    #[test]
+    fn test_effective_exclude_sections() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+exclude_sections: ["Human onboarding"]
+agents:
+  claude: true
+  cursor:
+    exclude_sections: ["Changelog"]
+    split_config:
+      rules:
+        - file_patterns: ["*design*"]
+          sections: ["API*"]
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            config.get_effective_exclude_sections("claude"),
+            vec!["Human onboarding".to_string()]
+        );
+        assert_eq!(
+            config.get_effective_exclude_sections("cursor"),
+            vec!["Human onboarding".to_string(), "Changelog".to_string()]
+        );
+        let CursorConfig::Advanced(cursor) = &config.agents.cursor else {
+            panic!("Expected advanced cursor config");
+        };
+        let rules = &cursor.split_config.as_ref().unwrap().rules;
+        assert_eq!(rules[0].sections, Some(vec!["API*".to_string()]));
+    }
+
+    #[test]
+    fn test_effective_toc() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+toc: true
+agents:
+  claude: true
+  github:
+    toc: false
+  windsurf: true
+  cursor:
+    toc: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert!(config.get_effective_toc("claude"));
+        assert!(config.get_effective_toc("codex"));
+        assert!(!config.get_effective_toc("github"));
+        // Rule-file agents ignore the global setting
+        assert!(!config.get_effective_toc("windsurf"));
+        assert!(config.get_effective_toc("cursor"));
+        assert!(!AIContextConfig::default().get_effective_toc("claude"));
+    }
+
+    #[test]
+    fn test_effective_docs_sources() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+docs_sources:
+  - ai-context/common
+  - path: ai-context/rust
+    prefix: rust
+agents:
+  claude: true
+  cursor:
+    base_docs_dir: ./cursor-docs
+  github:
+    docs_sources: [ai-context/github]
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+
+        let sources = config.get_effective_docs_sources(Some("claude")).unwrap();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].path(), "ai-context/common");
+        assert_eq!(sources[0].prefix(), None);
+        assert_eq!(sources[1].path(), "ai-context/rust");
+        assert_eq!(sources[1].prefix(), Some("rust"));
+
+        // Agent base_docs_dir takes precedence over global docs_sources
+        assert!(config.get_effective_docs_sources(Some("cursor")).is_none());
+        assert_eq!(
+            config.get_effective_docs_sources(Some("github")).unwrap(),
+            [DocsSource::Simple("ai-context/github".to_string())]
+        );
+        assert!(AIContextConfig::default()
+            .get_effective_docs_sources(None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_max_tokens_config() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+max_tokens_action: fail
+agents:
+  claude:
+    max_tokens: 8000
+  cursor: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.get_max_tokens("claude"), Some(8000));
+        assert_eq!(config.get_max_tokens("cursor"), None);
+        assert_eq!(config.get_max_tokens_action(), BudgetAction::Fail);
+        assert_eq!(
+            AIContextConfig::default().get_max_tokens_action(),
+            BudgetAction::Warn
+        );
+    }
+
+    #[test]
+    fn test_source_extensions() {
+        let mut config = AIContextConfig::default();
+        assert_eq!(config.get_source_extensions(), vec!["md"]);
+        assert_eq!(config.match_source_extension("guide.mdx"), None);
+        assert_eq!(
+            config.strip_source_extension("guides/testing.md"),
+            "guides/testing"
+        );
+
+        config.source_extensions = Some(vec![
+            "md".to_string(),
+            ".mdx".to_string(),
+            "markdown".to_string(),
+            "txt".to_string(),
+        ]);
+        assert_eq!(config.match_source_extension("guide.mdx"), Some("mdx"));
+        assert_eq!(config.match_source_extension("notes.txt"), Some("txt"));
+        assert_eq!(config.match_source_extension("md"), None);
+        assert_eq!(config.match_source_extension("image.png"), None);
+        assert_eq!(
+            config.strip_source_extension("api/intro.markdown"),
+            "api/intro"
+        );
+        assert_eq!(config.strip_source_extension("notes.txt"), "notes");
+    }
+
+    #[test]
+    fn test_split_sections_config_parsing() {
+        let yaml = r#"
+version: "1.0"
+output_mode: split
+base_docs_dir: ./docs
+split_sections:
+  heading_level: 3
+  min_tokens: 2000
+agents:
+  cursor: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        let split = config.split_sections.unwrap();
+        assert_eq!(split.get_heading_level(), 3);
+        assert_eq!(split.get_min_tokens(), 2000);
+
+        let split = SectionSplitConfig::default();
+        assert_eq!(split.get_heading_level(), 2);
+        assert_eq!(split.get_min_tokens(), 0);
+    }
+
+    #[test]
+    fn test_secret_scan_config_parsing() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+secret_scan:
+  entropy: false
+  patterns:
+    - name: internal-token
+      regex: "itk_[a-z0-9]{12}"
+  allowlist: ["^itk_test"]
+agents:
+  claude: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        let secret_scan = config.get_secret_scan().unwrap();
+        assert_eq!(secret_scan.entropy, Some(false));
+        assert_eq!(secret_scan.patterns[0].name, "internal-token");
+        assert_eq!(secret_scan.allowlist, vec!["^itk_test"]);
+
+        // Enabled by default, disabled explicitly
+        assert!(AIContextConfig::default().get_secret_scan().is_some());
+        let config = AIContextConfig {
+            secret_scan: Some(SecretScanConfig {
+                enabled: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(config.get_secret_scan().is_none());
+    }
+
+    #[test]
+    fn test_scan_limits_config_parsing() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+symlinks: follow_within_root
+max_depth: 4
+max_file_size: 65536
+agents:
+  claude: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.get_symlink_policy(), SymlinkPolicy::FollowWithinRoot);
+        assert_eq!(config.get_max_depth(), 4);
+        assert_eq!(config.get_max_file_size(), 65536);
+
+        let config = AIContextConfig::default();
+        assert_eq!(config.get_symlink_policy(), SymlinkPolicy::Follow);
+        assert_eq!(config.get_max_depth(), 32);
+        assert_eq!(config.get_max_file_size(), 1024 * 1024);
+    }
+
+    #[test]
+    fn test_encoding_config_parsing() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+source_encoding: shift_jis
+normalize_line_endings: false
+output_eol: crlf
+agents:
+  claude: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.source_encoding.as_deref(), Some("shift_jis"));
+        assert!(!config.get_normalize_line_endings());
+        assert_eq!(config.get_output_eol(), LineEnding::Crlf);
+        assert!(AIContextConfig::default().get_normalize_line_endings());
+        assert_eq!(AIContextConfig::default().get_output_eol(), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_heading_mode_parsing() {
+        assert_eq!(
+            AIContextConfig::default().get_heading_mode(),
+            HeadingMode::Keep
+        );
+
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./docs"
+heading_mode: doc_title
+agents:
+  claude: true
+"#;
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.get_heading_mode(), HeadingMode::DocTitle);
+    }
+
     #[test]
     fn test_simple_agent_config_serialization() {
         let cursor_config = CursorConfig::Simple(true);
@@ -1041,6 +2981,12 @@ mod tests {
             include_filenames: None,
             output_mode: Some(OutputMode::Split),
             base_docs_dir: None,
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
 
@@ -1219,6 +3165,12 @@ agents:
             output_mode: None,
             include_filenames: None,
             base_docs_dir: Some("./cursor-specific".to_string()),
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
 
@@ -1227,6 +3179,12 @@ agents:
             output_mode: None,
             include_filenames: None,
             base_docs_dir: Some("./cline-specific".to_string()),
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
         });
 
         // Agent-specific settings override global setting
@@ -1263,6 +3221,12 @@ agents:
             output_mode: Some(OutputMode::Split),
             include_filenames: None,
             base_docs_dir: Some("./custom-docs".to_string()),
+            docs_sources: None,
+            max_tokens: None,
+            toc: None,
+            include: None,
+            exclude: None,
+            exclude_sections: None,
             split_config: None,
         });
 
@@ -1426,4 +3390,71 @@ agents:
         );
         assert_eq!(split_config.rules[2].inclusion, InclusionMode::Manual);
     }
+
+    #[test]
+    fn test_windsurf_advanced_config_with_split_config() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./ai-context"
+agents:
+  windsurf:
+    output_mode: split
+    split_config:
+      rules:
+        - file_patterns: ["rust*"]
+          globs: ["**/*.rs"]
+        - file_patterns: ["review*"]
+          trigger: model_decision
+          description: "Code review checklist"
+"#;
+
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.enabled_agents(), vec!["windsurf"]);
+        assert_eq!(
+            config.get_effective_output_mode("windsurf"),
+            OutputMode::Split
+        );
+
+        let rules = &config
+            .agents
+            .windsurf
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+            .unwrap()
+            .rules;
+        assert_eq!(rules[0].trigger, None);
+        assert_eq!(rules[0].globs, Some(vec!["**/*.rs".to_string()]));
+        assert_eq!(rules[1].trigger, Some(WindsurfTrigger::ModelDecision));
+    }
+
+    #[test]
+    fn test_continue_config_uses_continue_key() {
+        let yaml = r#"
+version: "1.0"
+base_docs_dir: "./ai-context"
+agents:
+  continue:
+    split_config:
+      rules:
+        - file_patterns: ["rust*"]
+          globs: ["**/*.rs"]
+"#;
+
+        let config: AIContextConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.enabled_agents(), vec!["continue"]);
+        let split_config = config
+            .agents
+            .continue_dev
+            .get_advanced_config()
+            .and_then(|config| config.split_config.as_ref())
+            .unwrap();
+        assert_eq!(
+            split_config.rules[0].globs,
+            Some(vec!["**/*.rs".to_string()])
+        );
+
+        let serialized = serde_yaml::to_string(&config.agents).unwrap();
+        assert!(serialized.contains("continue:"));
+        assert!(!serialized.contains("continue_dev"));
+    }
 }